@@ -0,0 +1,174 @@
+use crate::{core::apps::LogEntry, prelude::*};
+use anyhow::{bail, Context, Result};
+
+const PROGRAM_ID: &str = "CWOS";
+const ADIF_VERSION: &str = "3.1.4";
+
+/// writes the log in the ADIF format, see https://adif.org
+pub fn export(log: &[LogEntry]) -> String {
+	let mut adif = format!(
+		"CWOS LOG\n{}{}<EOH>\n",
+		field("ADIF_VER", ADIF_VERSION),
+		field("PROGRAMID", PROGRAM_ID)
+	);
+
+	for entry in log {
+		let DateTime {
+			year,
+			month,
+			day,
+			hour,
+			minute,
+			second,
+		} = DateTime::from_epoch_ms(entry.time, 0);
+
+		adif += &field("CALL", &entry.call);
+		adif += &field("QSO_DATE", &format!("{year:04}{month:02}{day:02}"));
+		adif += &field("TIME_ON", &format!("{hour:02}{minute:02}{second:02}"));
+		adif += &field("MODE", "CW");
+		adif += &field("RST_SENT", &entry.rst_sent);
+		adif += &field("RST_RCVD", &entry.rst_rcvd);
+		adif += &field("COMMENT", &entry.notes);
+		adif += "<EOR>\n";
+	}
+
+	adif
+}
+
+/// reads the contacts of an ADIF file, fields other than the ones that are exported are ignored
+pub fn import(adif: &str) -> Result<Vec<LogEntry>> {
+	let mut log = vec![];
+	let mut fields: Vec<(String, String)> = vec![];
+	let mut rest = adif;
+
+	while let Some(start) = rest.find('<') {
+		let end = rest[start..]
+			.find('>')
+			.map(|end| start + end)
+			.context("unterminated ADIF tag")?;
+
+		let tag = &rest[start + 1..end];
+		rest = &rest[end + 1..];
+
+		// e.g. `CALL:6` or `QSO_DATE:8:D`
+		let mut parts = tag.split(':');
+		let name = parts.next().unwrap_or_default().to_ascii_uppercase();
+
+		match (name.as_str(), parts.next()) {
+			("EOH", _) => fields.clear(),
+			("EOR", _) => log.push(entry(&std::mem::take(&mut fields))?),
+			(_, Some(len)) => {
+				let len = len
+					.trim()
+					.parse::<usize>()
+					.with_context(|| format!("invalid length of ADIF field {name}"))?;
+				let value = rest
+					.get(..len)
+					.with_context(|| format!("ADIF field {name} is too short"))?;
+
+				fields.push((name, value.to_ascii_uppercase()));
+				rest = &rest[len..];
+			}
+			(_, None) => bail!("ADIF field {name} has no length"),
+		}
+	}
+
+	Ok(log)
+}
+
+/// e.g. `<CALL:6>DL1ABC `
+fn field(name: &str, value: &str) -> String {
+	match value.is_empty() {
+		true => String::new(),
+		false => format!("<{name}:{}>{value} ", value.len()),
+	}
+}
+
+fn entry(fields: &[(String, String)]) -> Result<LogEntry> {
+	let get = |name: &str| {
+		fields
+			.iter()
+			.find(|(field, _)| field == name)
+			.map(|(_, value)| value.trim().to_string())
+			.unwrap_or_default()
+	};
+
+	let call = get("CALL");
+	if call.is_empty() {
+		bail!("ADIF record without a CALL");
+	}
+
+	let (date, time) = (get("QSO_DATE"), get("TIME_ON"));
+	let number = |value: &str, range: std::ops::Range<usize>| -> Result<u32> {
+		value
+			.get(range)
+			.and_then(|digits| digits.parse().ok())
+			.with_context(|| format!("invalid date or time in ADIF record of {call}"))
+	};
+
+	let time = DateTime {
+		year: number(&date, 0..4)? as i64,
+		month: number(&date, 4..6)?,
+		day: number(&date, 6..8)?,
+		hour: number(&time, 0..2)?,
+		minute: number(&time, 2..4)?,
+		// the seconds are optional
+		second: number(&time, 4..6).unwrap_or_default(),
+	};
+
+	let notes = match get("COMMENT").is_empty() {
+		true => get("NOTES"),
+		false => get("COMMENT"),
+	};
+
+	Ok(LogEntry {
+		time: time.to_epoch_ms(),
+		call,
+		rst_sent: get("RST_SENT"),
+		rst_rcvd: get("RST_RCVD"),
+		notes,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn entry(time: u64, call: &str, notes: &str) -> LogEntry {
+		LogEntry {
+			time,
+			call: call.to_string(),
+			rst_sent: "599".to_string(),
+			rst_rcvd: "579".to_string(),
+			notes: notes.to_string(),
+		}
+	}
+
+	#[test]
+	fn round_trip() {
+		// 2024-03-15 14:35:20Z and 2024-03-16 08:00:00Z
+		let log = vec![
+			entry(1_710_513_320_000, "DL1ABC", "FB SIG"),
+			entry(1_710_576_000_000, "W1XYZ/P", ""),
+		];
+
+		assert_eq!(import(&export(&log)).unwrap(), log);
+	}
+
+	#[test]
+	fn lowercase_tag_without_seconds() {
+		let adif = "<eoh>\n<call:6>dl1abc <qso_date:8:D>20240315 <time_on:4>1435 \
+			<rst_sent:3>599 <rst_rcvd:3>579 <notes:6>fb sig <eor>";
+
+		let log = import(adif).unwrap();
+		assert_eq!(log, [entry(1_710_513_300_000, "DL1ABC", "FB SIG")]);
+	}
+
+	#[test]
+	fn record_without_call() {
+		let adif = "<QSO_DATE:8>20240315 <TIME_ON:6>143520 <EOR>";
+
+		let error = import(adif).unwrap_err();
+		assert_eq!(error.to_string(), "ADIF record without a CALL");
+	}
+}
@@ -1,11 +1,44 @@
 // idea: add clap
-// idea: provide config as file
 // idea: add modes: dot/dash mode, string mode etc
 
-use cwos::{core::apps::AppLauncher, prelude::*, std_context::StdContext};
-use std::io::stdin;
+use anyhow::{bail, Context};
+use cwos::{
+	adif,
+	core::apps::{AbbreviationSets, AppLauncher, LogEntry, ABBREVIATIONS_KEY, LOG_KEY},
+	fs_config::{config_path, parse_args},
+	prelude::*,
+	std_context::StdContext,
+};
+use std::{
+	fs::{read_to_string, write},
+	io::stdin,
+};
 
-pub fn main() {
+const USAGE: &str = "usage: cli [--config <path>] \
+	[export [<file.adi>] | import <file.adi> | abbreviations <file.json>]";
+
+pub fn main() -> anyhow::Result<()> {
+	let args = parse_args()?;
+	let mut ctx = StdContext::new(config_path(args.config)?)?;
+
+	let command = args.command.iter().map(String::as_str).collect::<Vec<_>>();
+
+	match command.as_slice() {
+		[] => run(&mut ctx),
+		["export"] => {
+			print!("{}", export(&ctx));
+			Ok(())
+		}
+		["export", path] => {
+			write(path, export(&ctx)).with_context(|| format!("failed to write {path}"))
+		}
+		["import", path] => import(&mut ctx, path),
+		["abbreviations", path] => load_abbreviations(&mut ctx, path),
+		_ => bail!("{USAGE}"),
+	}
+}
+
+fn run(ctx: &mut StdContext) -> anyhow::Result<()> {
 	let mut controller = AppLauncher::default();
 
 	loop {
@@ -15,11 +48,65 @@ pub fn main() {
 		let input_str = input_str.trim();
 
 		let input = CwString::from(input_str);
-		let mut ctx = StdContext;
-		let output = String::from(&controller.tick(&mut ctx, input));
 
-		println!("{output}");
+		// the reply, followed by any voices that are mixed in
+		for voice in controller.tick(ctx, input) {
+			println!("{}", String::from(&voice.text));
+		}
 
 		println!();
 	}
 }
+
+fn export(ctx: &StdContext) -> String {
+	let log = ctx.load::<Vec<LogEntry>>(LOG_KEY).unwrap_or_default();
+
+	adif::export(&log)
+}
+
+/// adds the contacts of an ADIF file to the log, skipping the ones that are already logged
+fn import(ctx: &mut StdContext, path: &str) -> anyhow::Result<()> {
+	let string = read_to_string(path).with_context(|| format!("failed to read {path}"))?;
+	let imported = adif::import(&string).with_context(|| format!("invalid ADIF file {path}"))?;
+
+	let mut log = ctx.load::<Vec<LogEntry>>(LOG_KEY).unwrap_or_default();
+	let count = log.len();
+
+	// ADIF times are accurate to the second
+	for entry in imported {
+		let is_logged = |logged: &LogEntry| {
+			logged.call == entry.call && logged.time / 1000 == entry.time / 1000
+		};
+
+		if !log.iter().any(is_logged) {
+			log.push(entry);
+		}
+	}
+
+	log.sort_by_key(|entry| entry.time);
+	ctx.store(LOG_KEY, &log)?;
+
+	println!("imported {} contacts", log.len() - count);
+	Ok(())
+}
+
+/// adds the abbreviation sets of a file to the dictionary, replacing sets with the same name
+///
+/// => the file has the shape of `assets/abbreviations.json`
+fn load_abbreviations(ctx: &mut StdContext, path: &str) -> anyhow::Result<()> {
+	let string = read_to_string(path).with_context(|| format!("failed to read {path}"))?;
+	let loaded = serde_json::from_str::<AbbreviationSets>(&string)
+		.with_context(|| format!("invalid abbreviations file {path}"))?;
+
+	let mut sets = ctx
+		.load::<AbbreviationSets>(ABBREVIATIONS_KEY)
+		.unwrap_or_default();
+
+	let count = loaded.len();
+	sets.extend(loaded);
+	ctx.store(ABBREVIATIONS_KEY, &sets)?;
+
+	println!("loaded {count} abbreviation sets");
+
+	Ok(())
+}
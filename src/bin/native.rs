@@ -1,7 +1,19 @@
-use cwos::ui::{create_app, load_icon};
+use cwos::{
+	fs_config::{config_path, parse_args},
+	std_context::StdContext,
+	ui::{create_app, load_icon},
+};
 use eframe::egui;
 
-fn main() -> eframe::Result<()> {
+fn main() -> anyhow::Result<()> {
+	let args = parse_args()?;
+
+	if let Some(arg) = args.command.first() {
+		anyhow::bail!("unknown argument: {arg}");
+	}
+
+	let cw_ctx = StdContext::new(config_path(args.config)?)?;
+
 	let icon = load_icon();
 	let mut viewport = egui::ViewportBuilder::default().with_inner_size([320.0, 240.0]);
 
@@ -20,7 +32,8 @@ fn main() -> eframe::Result<()> {
 		options,
 		Box::new(|cc| {
 			cc.egui_ctx.set_theme(egui::Theme::Dark);
-			Ok(Box::new(create_app()))
+			Ok(Box::new(create_app(cw_ctx)))
 		}),
 	)
+	.map_err(|err| anyhow::anyhow!("{err}"))
 }
@@ -0,0 +1,156 @@
+use super::{idk, CwApp};
+use crate::prelude::*;
+use std::{iter::Peekable, str::Chars};
+
+/// evaluates arithmetic expressions like `(2+3)X4` or `7/2=`
+///
+/// => `X` multiplies, the usual precedence applies and parentheses may be used
+#[derive(Default)]
+pub struct Calculator;
+
+impl CwApp for Calculator {
+	fn name(&self) -> &'static str {
+		"CA"
+	}
+
+	fn description(&self) -> &'static str {
+		"CALCULATOR"
+	}
+
+	fn usage(&self) -> &'static str {
+		"KEY AN EXPRESSION LIKE (2+3)X4 = USE X TO MULTIPLY"
+	}
+
+	fn tick(&mut self, _ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input).replace(' ', "");
+		let expression = input.strip_suffix('=').unwrap_or(&input);
+
+		if expression.is_empty() {
+			return Default::default();
+		}
+
+		match evaluate(expression) {
+			Some(result) => CwString::from(format_number(result).as_str()),
+			None => idk(),
+		}
+	}
+}
+
+fn evaluate(expression: &str) -> Option<f64> {
+	let mut chars = expression.chars().peekable();
+	let result = parse_sum(&mut chars)?;
+
+	match chars.next() {
+		None if result.is_finite() => Some(result),
+		_ => None,
+	}
+}
+
+/// sum = product (('+' | '-') product)*
+fn parse_sum(chars: &mut Peekable<Chars>) -> Option<f64> {
+	let mut result = parse_product(chars)?;
+
+	while let Some(&operator @ ('+' | '-')) = chars.peek() {
+		chars.next();
+		let operand = parse_product(chars)?;
+
+		match operator {
+			'+' => result += operand,
+			_ => result -= operand,
+		}
+	}
+
+	Some(result)
+}
+
+/// product = factor (('X' | '/') factor)*
+fn parse_product(chars: &mut Peekable<Chars>) -> Option<f64> {
+	let mut result = parse_factor(chars)?;
+
+	while let Some(&operator @ ('X' | '/')) = chars.peek() {
+		chars.next();
+		let operand = parse_factor(chars)?;
+
+		match operator {
+			'X' => result *= operand,
+			_ if operand == 0.0 => return None,
+			_ => result /= operand,
+		}
+	}
+
+	Some(result)
+}
+
+/// factor = '-' factor | '(' sum ')' | number
+fn parse_factor(chars: &mut Peekable<Chars>) -> Option<f64> {
+	match chars.peek()? {
+		'-' => {
+			chars.next();
+			Some(-parse_factor(chars)?)
+		}
+		'(' => {
+			chars.next();
+			let result = parse_sum(chars)?;
+			(chars.next()? == ')').then_some(result)
+		}
+		_ => {
+			let mut number = String::new();
+
+			while let Some(&c @ ('0'..='9' | '.')) = chars.peek() {
+				number.push(c);
+				chars.next();
+			}
+
+			number.parse().ok()
+		}
+	}
+}
+
+/// e.g. `12`, `-3.5` or `0.3333`
+fn format_number(number: f64) -> String {
+	let number = format!("{number:.4}");
+	let number = number.trim_end_matches('0').trim_end_matches('.');
+
+	match number {
+		"-0" => "0".to_string(),
+		number => number.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sim_context::SimContext;
+
+	fn calculate(expression: &str) -> String {
+		let mut ctx = SimContext::default();
+		String::from(&Calculator.tick(&mut ctx, CwString::from(expression)))
+	}
+
+	#[test]
+	fn precedence() {
+		assert_eq!(calculate("2+3X4"), "14");
+		assert_eq!(calculate("10-4/2"), "8");
+		assert_eq!(calculate("7/2="), "3.5");
+	}
+
+	#[test]
+	fn parentheses() {
+		assert_eq!(calculate("(2+3)X4="), "20");
+		assert_eq!(calculate("2X(3+(4-1))"), "12");
+	}
+
+	#[test]
+	fn unary_minus() {
+		assert_eq!(calculate("-3+5"), "2");
+		assert_eq!(calculate("2X-(1+2)"), "-6");
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(calculate("1/0"), "?");
+		assert_eq!(calculate("2+"), "?");
+		assert_eq!(calculate("(2+3"), "?");
+		assert_eq!(calculate("2+3)"), "?");
+	}
+}
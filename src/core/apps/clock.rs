@@ -0,0 +1,100 @@
+use super::{idk, CwApp};
+use crate::prelude::*;
+
+/// replies with the current time or date
+///
+/// ## Queries
+/// - `T`: time, local or UTC depending on the config
+/// - `T D`: time and date, which is also sent right away
+/// - `Z`: UTC time
+/// - `D`: date
+/// - `12` / `24`: time on the 12-hour / 24-hour clock
+///
+/// => UTC is marked with `Z`, it is used as long as no UTC offset is configured
+#[derive(Default)]
+pub struct Clock;
+
+impl CwApp for Clock {
+	fn name(&self) -> &'static str {
+		"CK"
+	}
+
+	fn description(&self) -> &'static str {
+		"TIME AND DATE"
+	}
+
+	fn usage(&self) -> &'static str {
+		"T TIME = Z UTC = D DATE = 12 OR 24 CLOCK"
+	}
+
+	/// replies with the time and date right away
+	fn enter(&mut self, ctx: &mut dyn CwContext, args: CwString) -> CwString {
+		match args.0.is_empty() {
+			true => self.tick(ctx, CwString::from("T D")),
+			false => self.tick(ctx, args),
+		}
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		use CwSymbol::*;
+
+		let config = ctx.config().clock;
+		let time = ctx.time();
+
+		let reply = match input.0.as_slice() {
+			[] => return Default::default(),
+			[T] => Self::time(time, config.hour_12, config.utc, &config),
+			[T, Space, D] => format!(
+				"{} {}",
+				Self::time(time, config.hour_12, config.utc, &config),
+				Self::date(time, config.utc, &config)
+			),
+			[Z] => Self::time(time, config.hour_12, true, &config),
+			[D] => Self::date(time, config.utc, &config),
+			[CwSymbol::_1, CwSymbol::_2] => Self::time(time, true, config.utc, &config),
+			[CwSymbol::_2, CwSymbol::_4] => Self::time(time, false, config.utc, &config),
+			_ => return idk(),
+		};
+
+		CwString::from(reply.as_str())
+	}
+}
+
+impl Clock {
+	/// the local time, or UTC if it is asked for or no UTC offset is configured
+	fn date_time(time: u64, utc: bool, config: &ClockConfig) -> (DateTime, bool) {
+		match (utc, config.utc_offset_min) {
+			(false, Some(utc_offset_min)) => (DateTime::from_epoch_ms(time, utc_offset_min), false),
+			_ => (DateTime::from_epoch_ms(time, 0), true),
+		}
+	}
+
+	/// e.g. `1435`, `235 PM` or `1435Z`
+	fn time(time: u64, hour_12: bool, utc: bool, config: &ClockConfig) -> String {
+		let (DateTime { hour, minute, .. }, utc) = Self::date_time(time, utc, config);
+		let zone = if utc { "Z" } else { "" };
+
+		if hour_12 {
+			let suffix = if hour < 12 { "AM" } else { "PM" };
+			let hour = match hour % 12 {
+				0 => 12,
+				hour => hour,
+			};
+			format!("{hour}{minute:02}{zone} {suffix}")
+		} else {
+			format!("{hour:02}{minute:02}{zone}")
+		}
+	}
+
+	/// e.g. `2024-12-31`
+	fn date(time: u64, utc: bool, config: &ClockConfig) -> String {
+		let (
+			DateTime {
+				year, month, day, ..
+			},
+			_,
+		) = Self::date_time(time, utc, config);
+
+		format!("{year}-{month:02}-{day:02}")
+	}
+}
@@ -0,0 +1,251 @@
+use super::{words::random_callsign, CwApp};
+use crate::{prelude::*, wpm};
+use serde::{Deserialize, Serialize};
+
+const HIGH_SCORES_KEY: &str = "contest_high_scores";
+const HIGH_SCORES: usize = 5;
+
+const MAX_CALLERS: usize = 4;
+
+/// ms per hour, for the QSO rate
+const HOUR_MS: u64 = 3_600_000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Score {
+	qsos: u32,
+	/// QSOs per hour
+	rate: u32,
+	/// correct answers in percent
+	accuracy: u32,
+}
+
+impl Score {
+	/// e.g. `QSO 12 RATE 180 ACC 92`
+	fn format(&self) -> String {
+		let Score {
+			qsos,
+			rate,
+			accuracy,
+		} = self;
+
+		format!("QSO {qsos} RATE {rate} ACC {accuracy}")
+	}
+}
+
+#[derive(Debug, Default)]
+struct Session {
+	start: u64,
+	qsos: u32,
+	answers: u32,
+}
+
+/// a station in the pileup, calling with its own speed and tone
+#[derive(Debug, Clone)]
+struct Caller {
+	call: String,
+	signal: SignalConfig,
+	delay_ms: u32,
+}
+
+/// contest exchange trainer
+///
+/// simulates a pileup of up to four stations that call at different speeds and tones,
+/// the operator picks one of them and keys back its call and the exchange
+///
+/// ## Exchange
+/// the call, `5NN` and the serial number of the QSO, counting from 1 each session, e.g. `DL1ABC 5NN 1`
+///
+/// => the worked station replies with `TU`, mistakes are flagged with `ERR CALL` or `ERR NR`,
+/// then the pileup calls again
+///
+/// ## Commands
+/// - `?` / `AGN`: the pileup calls again
+/// - `S`: the score of the current session, e.g. `QSO 12 RATE 180 ACC 92`
+/// - `H`: the high scores
+///
+/// => the score is added to the high scores when the app is left
+#[derive(Default)]
+pub struct Contest {
+	session: Session,
+	callers: Vec<Caller>,
+	pileup_pending: bool,
+}
+
+impl CwApp for Contest {
+	fn name(&self) -> &'static str {
+		"CT"
+	}
+
+	fn description(&self) -> &'static str {
+		"CONTEST PILEUP TRAINER"
+	}
+
+	fn usage(&self) -> &'static str {
+		"KEY BACK ONE CALL WITH 5NN AND YOUR SERIAL NR = AGN PILEUP = S SCORE = H HIGH SCORES"
+	}
+
+	fn enter(&mut self, ctx: &mut dyn CwContext, args: CwString) -> CwString {
+		self.session = Session {
+			start: ctx.time(),
+			..Default::default()
+		};
+		self.callers.clear();
+		self.join_callers(ctx);
+
+		match args.0.is_empty() {
+			true => CwString::from(self.name()),
+			false => self.tick(ctx, args),
+		}
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let words = input.split_whitespace().collect::<Vec<_>>();
+
+		let reply = match words.as_slice() {
+			[] => return Default::default(),
+			["?" | "AGN"] => {
+				self.pileup_pending = true;
+				String::new()
+			}
+			["S"] => self.score(ctx).format(),
+			["H"] => Self::high_scores(ctx),
+			_ => self.answer(ctx, &words),
+		};
+
+		CwString::from(reply.as_str())
+	}
+
+	fn mix(&mut self, _ctx: &mut dyn CwContext) -> Vec<Voice> {
+		if !std::mem::take(&mut self.pileup_pending) {
+			return vec![];
+		}
+
+		self.callers
+			.iter()
+			.map(|caller| Voice {
+				text: CwString::from(caller.call.as_str()),
+				signal: Some(caller.signal),
+				delay_ms: caller.delay_ms,
+			})
+			.collect()
+	}
+
+	fn exit(&mut self, ctx: &mut dyn CwContext) {
+		let score = self.score(ctx);
+
+		if score.qsos == 0 {
+			return;
+		}
+
+		let mut high_scores = ctx.load::<Vec<Score>>(HIGH_SCORES_KEY).unwrap_or_default();
+
+		high_scores.push(score);
+		high_scores.sort_by_key(|score| std::cmp::Reverse((score.qsos, score.rate)));
+		high_scores.truncate(HIGH_SCORES);
+
+		// => nothing can be sent when leaving, so a failed save only loses the score
+		ctx.store(HIGH_SCORES_KEY, &high_scores).ok();
+	}
+}
+
+impl Contest {
+	/// the operator answers one of the callers
+	fn answer(&mut self, ctx: &mut dyn CwContext, words: &[&str]) -> String {
+		let serial = self.session.qsos + 1;
+		self.session.answers += 1;
+
+		let caller = self
+			.callers
+			.iter()
+			.position(|caller| words.contains(&caller.call.as_str()));
+
+		// the serial number is the last number, cut numbers like `T` for 0 and `N` for 9 included
+		let number = words.iter().rev().find_map(|word| Self::parse_number(word));
+
+		let mut mistakes = vec![];
+		if caller.is_none() {
+			mistakes.push("CALL");
+		}
+		if number != Some(serial) {
+			mistakes.push("NR");
+		}
+
+		self.pileup_pending = true;
+
+		match (caller, mistakes.is_empty()) {
+			(Some(index), true) => {
+				self.session.qsos += 1;
+				self.callers.remove(index);
+				self.join_callers(ctx);
+				"TU".to_string()
+			}
+			_ => format!("ERR {}", mistakes.join(" ")),
+		}
+	}
+
+	/// one or two stations join the pileup, at 18 to 35 WPM and up to 200 Hz off the output tone
+	fn join_callers(&mut self, ctx: &mut dyn CwContext) {
+		let output = ctx.config().output.signal;
+
+		for _ in 0..1 + ctx.random_index(2) {
+			if self.callers.len() >= MAX_CALLERS {
+				break;
+			}
+
+			let wpm = 18 + ctx.random_index(18) as u32;
+			let signal = SignalConfig {
+				unit_ms: wpm!(wpm),
+				fw_ms: wpm!(wpm),
+				freq: output.freq.saturating_sub(200) + 50 * ctx.random_index(9) as u32,
+			};
+
+			self.callers.push(Caller {
+				call: random_callsign(ctx),
+				signal,
+				delay_ms: 100 * ctx.random_index(10) as u32,
+			});
+		}
+
+		self.pileup_pending = true;
+	}
+
+	fn score(&self, ctx: &dyn CwContext) -> Score {
+		let Session {
+			start,
+			qsos,
+			answers,
+		} = self.session;
+
+		let elapsed_ms = ctx.time().saturating_sub(start).max(1);
+
+		Score {
+			qsos,
+			rate: (qsos as u64 * HOUR_MS / elapsed_ms) as u32,
+			accuracy: (qsos * 100).checked_div(answers).unwrap_or_default(),
+		}
+	}
+
+	/// e.g. `QSO 12 RATE 180 ACC 92 = QSO 8 RATE 120 ACC 100`
+	fn high_scores(ctx: &dyn CwContext) -> String {
+		let high_scores = ctx.load::<Vec<Score>>(HIGH_SCORES_KEY).unwrap_or_default();
+
+		match high_scores.is_empty() {
+			true => "NIL".to_string(),
+			false => high_scores
+				.iter()
+				.map(Score::format)
+				.collect::<Vec<_>>()
+				.join(" = "),
+		}
+	}
+
+	fn parse_number(word: &str) -> Option<u32> {
+		let digits = word.replace('T', "0").replace('N', "9");
+
+		match digits.chars().all(|c| c.is_ascii_digit()) {
+			true => digits.parse().ok(),
+			false => None,
+		}
+	}
+}
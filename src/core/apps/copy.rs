@@ -0,0 +1,187 @@
+use super::{
+	idk,
+	words::{align, random_callsign, random_number, random_word},
+	CwApp,
+};
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+const CONFUSION_KEY: &str = "copy_confusion";
+
+/// stands in for characters that were not copied at all
+const MISSING: char = '~';
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum CopyMode {
+	#[default]
+	Words,
+	Callsigns,
+	Numbers,
+}
+
+/// sent character => copied character => count
+type Confusion = BTreeMap<char, BTreeMap<char, u32>>;
+
+/// receive practice
+///
+/// sends random words, callsigns or numbers, which the operator keys back,
+/// then replies with `R` or the mistakes, followed by the next item
+///
+/// ## Mistakes
+/// each mistake is sent as the copied character, `[HH]` and the right character, e.g. `H*S`
+///
+/// ## Commands
+/// - `CP W` / `CP C` / `CP N`: practice words, callsigns or numbers
+/// - `? S`: what `S` was mistaken for, e.g. `S H 5 I 2`
+/// - `?`: the most frequent mistakes
+///
+/// => characters that were not copied at all are sent as `~`
+#[derive(Default)]
+pub struct CopyPractice {
+	mode: CopyMode,
+	sent: String,
+}
+
+impl CwApp for CopyPractice {
+	fn name(&self) -> &'static str {
+		"CP"
+	}
+
+	fn description(&self) -> &'static str {
+		"COPY PRACTICE"
+	}
+
+	fn usage(&self) -> &'static str {
+		"CP W WORDS = CP C CALLS = CP N NUMBERS = KEY BACK WHAT YOU HEAR = ? S FOR MISTAKES OF S"
+	}
+
+	fn enter(&mut self, ctx: &mut dyn CwContext, args: CwString) -> CwString {
+		self.mode = match String::from(&args).as_str() {
+			"" | "W" => CopyMode::Words,
+			"C" => CopyMode::Callsigns,
+			"N" => CopyMode::Numbers,
+			_ => return idk(),
+		};
+
+		CwString::from(self.next_item(ctx).as_str())
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let copy = String::from(&input);
+		let words = copy.split_whitespace().collect::<Vec<_>>();
+
+		let reply = match words.as_slice() {
+			[] => return Default::default(),
+			["?"] => Self::top_mistakes(ctx),
+			["?", sent] if sent.chars().count() == 1 => Self::mistakes_of(ctx, sent),
+			_ => {
+				let copy = words.concat();
+				let Some(mistakes) = self.grade(ctx, &copy) else {
+					return idk();
+				};
+				format!("{mistakes} = {}", self.next_item(ctx))
+			}
+		};
+
+		CwString::from(reply.as_str())
+	}
+}
+
+impl CopyPractice {
+	fn next_item(&mut self, ctx: &mut dyn CwContext) -> String {
+		self.sent = match self.mode {
+			CopyMode::Words => random_word(ctx),
+			CopyMode::Callsigns => random_callsign(ctx),
+			CopyMode::Numbers => random_number(ctx),
+		};
+
+		self.sent.clone()
+	}
+
+	/// aligns the copy with the sent item and records the mistakes
+	///
+	/// => extra copied characters are sent as mistakes, but not recorded
+	fn grade(&self, ctx: &mut dyn CwContext, copy: &str) -> Option<String> {
+		let mut confusion = ctx.load::<Confusion>(CONFUSION_KEY).unwrap_or_default();
+		let mut mistakes = vec![];
+
+		for (sent_char, copied_char) in align(&self.sent, copy) {
+			if sent_char == copied_char {
+				continue;
+			}
+
+			mistakes.push(format!(
+				"{}*{}",
+				copied_char.map(String::from).unwrap_or_default(),
+				sent_char.map(String::from).unwrap_or_default(),
+			));
+
+			if let Some(sent_char) = sent_char {
+				*confusion
+					.entry(sent_char)
+					.or_default()
+					.entry(copied_char.unwrap_or(MISSING))
+					.or_default() += 1;
+			}
+		}
+
+		ctx.store(CONFUSION_KEY, &confusion).ok()?;
+
+		match mistakes.is_empty() {
+			true => Some("R".to_string()),
+			false => Some(mistakes.join(" ")),
+		}
+	}
+
+	/// e.g. `S H 5 I 2`
+	fn mistakes_of(ctx: &mut dyn CwContext, sent: &str) -> String {
+		let confusion = ctx.load::<Confusion>(CONFUSION_KEY).unwrap_or_default();
+		let sent_char = sent.chars().next().unwrap_or_default();
+
+		let mut mistakes = confusion
+			.get(&sent_char)
+			.map(|copied| copied.iter().collect::<Vec<_>>())
+			.unwrap_or_default();
+
+		if mistakes.is_empty() {
+			return "NIL".to_string();
+		}
+
+		mistakes.sort_by(|a, b| b.1.cmp(a.1));
+
+		let mistakes = mistakes
+			.iter()
+			.map(|(copied_char, count)| format!("{copied_char} {count}"))
+			.collect::<Vec<_>>()
+			.join(" ");
+
+		format!("{sent_char} {mistakes}")
+	}
+
+	/// e.g. `S H 5 = B 6 2`
+	fn top_mistakes(ctx: &mut dyn CwContext) -> String {
+		let confusion = ctx.load::<Confusion>(CONFUSION_KEY).unwrap_or_default();
+
+		let mut mistakes = confusion
+			.iter()
+			.flat_map(|(sent_char, copied)| {
+				copied
+					.iter()
+					.map(move |(copied_char, count)| (sent_char, copied_char, count))
+			})
+			.collect::<Vec<_>>();
+
+		if mistakes.is_empty() {
+			return "NIL".to_string();
+		}
+
+		mistakes.sort_by(|a, b| b.2.cmp(a.2));
+
+		mistakes
+			.iter()
+			.take(3)
+			.map(|(sent_char, copied_char, count)| format!("{sent_char} {copied_char} {count}"))
+			.collect::<Vec<_>>()
+			.join(" = ")
+	}
+}
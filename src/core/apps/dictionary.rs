@@ -0,0 +1,153 @@
+use super::{idk, CwApp};
+use crate::prelude::*;
+use std::{collections::BTreeMap, sync::OnceLock};
+
+/// sets of abbreviations loaded into the database, by name
+///
+/// => they take precedence over the built-in sets, e.g. `{"CONTEST": {"TU": "THANK YOU"}}`
+pub const ABBREVIATIONS_KEY: &str = "abbreviations";
+
+/// the set that `+` and `-` edit, it takes precedence over all others
+const CUSTOM_SET: &str = "CUSTOM";
+
+const BUILT_IN_SETS: &str = include_str!("../../../assets/abbreviations.json");
+
+/// set name => abbreviation => expansion
+pub type AbbreviationSets = BTreeMap<String, BTreeMap<String, String>>;
+
+/// looks up Q-codes and CW abbreviations
+///
+/// ## Commands
+/// - `QTH`: the expansion of an abbreviation, `QRS?` works too
+/// - `TNX FB`: the expansions of several abbreviations, e.g. `THANKS = FINE BUSINESS`
+/// - `? POWER`: the abbreviations whose expansion contains a keyword, e.g. `QRO QRP`
+/// - `+ HPE HOPE`: adds an abbreviation or replaces the expansion of an existing one
+/// - `- HPE`: removes an added abbreviation
+///
+/// ## Sets
+/// the built-in Q-codes and abbreviations are kept in `assets/abbreviations.json`,
+/// further sets of the same shape can be loaded into the database,
+/// the added abbreviations form the `CUSTOM` set
+///
+/// => unknown abbreviations are sent as `?`
+#[derive(Default)]
+pub struct Dictionary;
+
+impl CwApp for Dictionary {
+	fn name(&self) -> &'static str {
+		"DI"
+	}
+
+	fn description(&self) -> &'static str {
+		"Q CODES AND ABBREVIATIONS"
+	}
+
+	fn usage(&self) -> &'static str {
+		"KEY AN ABBREVIATION = ? WORD REVERSE LOOKUP = + ABBR TEXT ADD = - ABBR REMOVE"
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let words = input.split_whitespace().collect::<Vec<_>>();
+		let mut sets = ctx
+			.load::<AbbreviationSets>(ABBREVIATIONS_KEY)
+			.unwrap_or_default();
+
+		let reply = match words.as_slice() {
+			[] => return Default::default(),
+			["?", keywords @ ..] if !keywords.is_empty() => {
+				let matches = Self::table(&sets)
+					.into_iter()
+					.filter(|(_, expansion)| {
+						let expansion = expansion.split_whitespace().collect::<Vec<_>>();
+						keywords.iter().all(|keyword| expansion.contains(keyword))
+					})
+					.map(|(abbreviation, _)| abbreviation)
+					.collect::<Vec<_>>();
+
+				match matches.is_empty() {
+					true => "NIL".to_string(),
+					false => matches.join(" "),
+				}
+			}
+			["+", abbreviation, expansion @ ..] if !expansion.is_empty() => {
+				sets.entry(CUSTOM_SET.to_string())
+					.or_default()
+					.insert(abbreviation.to_string(), expansion.join(" "));
+				if ctx.store(ABBREVIATIONS_KEY, &sets).is_err() {
+					return idk();
+				}
+				"R".to_string()
+			}
+			["-", abbreviation] => {
+				let custom = sets.entry(CUSTOM_SET.to_string()).or_default();
+				if custom.remove(*abbreviation).is_none() {
+					return idk();
+				}
+
+				if ctx.store(ABBREVIATIONS_KEY, &sets).is_err() {
+					return idk();
+				}
+				"R".to_string()
+			}
+			abbreviations => {
+				let table = Self::table(&sets);
+
+				let expansions = abbreviations
+					.iter()
+					.map(|abbreviation| {
+						// Q-codes are asked as questions, e.g. `QRL?`
+						let abbreviation = abbreviation.trim_end_matches('?');
+						table.get(abbreviation).cloned()
+					})
+					.collect::<Option<Vec<_>>>();
+
+				match expansions {
+					Some(expansions) => expansions.join(" = "),
+					None => return idk(),
+				}
+			}
+		};
+
+		CwString::from(reply.as_str())
+	}
+}
+
+impl Dictionary {
+	/// the built-in sets with the loaded ones on top, and the custom set on top of all
+	fn table(sets: &AbbreviationSets) -> BTreeMap<String, String> {
+		let loaded = sets.iter().filter(|(name, _)| *name != CUSTOM_SET);
+		let custom = sets.get_key_value(CUSTOM_SET);
+
+		Self::built_in()
+			.iter()
+			.chain(loaded)
+			.chain(custom)
+			.flat_map(|(_, set)| set.clone())
+			.collect()
+	}
+
+	/// parsed once, the asset is checked by the tests, so it is never expected to be invalid
+	fn built_in() -> &'static AbbreviationSets {
+		static BUILT_IN: OnceLock<AbbreviationSets> = OnceLock::new();
+
+		BUILT_IN.get_or_init(|| serde_json::from_str(BUILT_IN_SETS).unwrap_or_default())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn built_in_sets() {
+		let sets = serde_json::from_str::<AbbreviationSets>(BUILT_IN_SETS).unwrap();
+
+		assert_eq!(
+			sets.keys().collect::<Vec<_>>(),
+			["ABBREVIATIONS", "Q CODES"]
+		);
+		assert_eq!(sets["Q CODES"]["QTH"], "LOCATION");
+		assert_eq!(Dictionary::built_in(), &sets);
+	}
+}
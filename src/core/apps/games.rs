@@ -0,0 +1,276 @@
+use super::{idk, words::random_word, CwApp};
+use crate::prelude::*;
+
+const LIVES: u32 = 6;
+
+/// the highest number of the number guessing game
+const MAX_NUMBER: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Game {
+	Hangman {
+		word: String,
+		guessed: Vec<char>,
+		lives: u32,
+	},
+	Number {
+		number: u32,
+		tries: u32,
+	},
+}
+
+impl Default for Game {
+	fn default() -> Self {
+		Self::Number {
+			number: 0,
+			tries: 0,
+		}
+	}
+}
+
+/// small games to keep practice engaging
+///
+/// ## Hangman
+/// `GA H` or `GA` sends the word as a pattern with `?` for every letter that wasn't guessed yet,
+/// followed by the lives left, e.g. `R??IO 5`
+///
+/// => guess a letter by keying it, or the whole word
+///
+/// ## Number guessing
+/// `GA N` thinks of a number from 1 to 100, guesses are answered with `UP` or `DN`
+///
+/// => once a game is over, the next one starts right away
+#[derive(Default)]
+pub struct Games {
+	game: Game,
+}
+
+impl CwApp for Games {
+	fn name(&self) -> &'static str {
+		"GA"
+	}
+
+	fn description(&self) -> &'static str {
+		"HANGMAN AND NUMBER GUESSING"
+	}
+
+	fn usage(&self) -> &'static str {
+		"GA H HANGMAN = GA N NUMBER GUESSING = KEY YOUR GUESS = ? REPEAT"
+	}
+
+	fn enter(&mut self, ctx: &mut dyn CwContext, args: CwString) -> CwString {
+		let reply = match String::from(&args).as_str() {
+			"" | "H" => self.new_hangman(ctx),
+			"N" => self.new_number(ctx),
+			_ => return idk(),
+		};
+
+		CwString::from(reply.as_str())
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let guess = input.split_whitespace().collect::<String>();
+
+		if guess.is_empty() {
+			return Default::default();
+		}
+
+		let reply = match self.game {
+			_ if guess == "?" => self.status(),
+			Game::Hangman { .. } => self.guess_letter(ctx, &guess),
+			Game::Number { .. } => match guess.parse::<u32>() {
+				Ok(guess) => self.guess_number(ctx, guess),
+				Err(_) => return idk(),
+			},
+		};
+
+		CwString::from(reply.as_str())
+	}
+}
+
+impl Games {
+	fn new_hangman(&mut self, ctx: &mut dyn CwContext) -> String {
+		self.game = Game::Hangman {
+			word: random_word(ctx),
+			guessed: vec![],
+			lives: LIVES,
+		};
+
+		self.status()
+	}
+
+	fn new_number(&mut self, ctx: &mut dyn CwContext) -> String {
+		self.game = Game::Number {
+			number: 1 + ctx.random_index(MAX_NUMBER as usize) as u32,
+			tries: 0,
+		};
+
+		self.status()
+	}
+
+	/// e.g. `R??IO 5` or `NR 1 100`
+	fn status(&self) -> String {
+		match &self.game {
+			Game::Hangman {
+				word,
+				guessed,
+				lives,
+			} => {
+				let pattern = word
+					.chars()
+					.map(|c| if guessed.contains(&c) { c } else { '?' })
+					.collect::<String>();
+
+				format!("{pattern} {lives}")
+			}
+			Game::Number { .. } => format!("NR 1 {MAX_NUMBER}"),
+		}
+	}
+
+	/// a single character guesses a letter, anything longer the whole word
+	fn guess_letter(&mut self, ctx: &mut dyn CwContext, guess: &str) -> String {
+		let Game::Hangman {
+			word,
+			guessed,
+			lives,
+		} = &mut self.game
+		else {
+			return self.status();
+		};
+
+		let mut chars = guess.chars();
+		let is_correct = match (chars.next(), chars.next()) {
+			(Some(letter), None) => {
+				let is_new = !guessed.contains(&letter);
+				guessed.push(letter);
+
+				// guessing a letter twice costs no life
+				!is_new || word.contains(letter)
+			}
+			_ => {
+				let is_word = guess == word;
+				if is_word {
+					guessed.extend(word.chars());
+				}
+
+				is_word
+			}
+		};
+
+		if !is_correct {
+			*lives -= 1;
+		}
+
+		if word.chars().all(|c| guessed.contains(&c)) {
+			let word = word.clone();
+			return format!("WIN {word} = {}", self.new_hangman(ctx));
+		}
+
+		if *lives == 0 {
+			let word = word.clone();
+			return format!("LOST {word} = {}", self.new_hangman(ctx));
+		}
+
+		self.status()
+	}
+
+	/// answers whether the number is higher (`UP`) or lower (`DN`) than the guess
+	fn guess_number(&mut self, ctx: &mut dyn CwContext, guess: u32) -> String {
+		let Game::Number { number, tries } = &mut self.game else {
+			return self.status();
+		};
+
+		*tries += 1;
+
+		match guess.cmp(number) {
+			std::cmp::Ordering::Less => "UP".to_string(),
+			std::cmp::Ordering::Greater => "DN".to_string(),
+			std::cmp::Ordering::Equal => {
+				let tries = *tries;
+				format!("R {tries} TRIES = {}", self.new_number(ctx))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sim_context::SimContext;
+
+	fn send(app: &mut Games, ctx: &mut SimContext, input: &str) -> String {
+		String::from(&app.tick(ctx, CwString::from(input)))
+	}
+
+	/// the seeded context always picks `OUT` as the first word
+	fn hangman(ctx: &mut SimContext) -> Games {
+		let mut app = Games::default();
+		let reply = String::from(&app.enter(ctx, CwString::from("H")));
+
+		assert_eq!(reply, "??? 6");
+		app
+	}
+
+	/// guesses the number by bisection, returns the final reply
+	fn guess_number(ctx: &mut SimContext) -> String {
+		let mut app = Games::default();
+		app.enter(ctx, CwString::from("N"));
+
+		let (mut low, mut high) = (1, MAX_NUMBER);
+
+		loop {
+			let guess = (low + high) / 2;
+
+			match send(&mut app, ctx, &guess.to_string()).as_str() {
+				"UP" => low = guess + 1,
+				"DN" => high = guess - 1,
+				reply => return reply.to_string(),
+			}
+		}
+	}
+
+	#[test]
+	fn hangman_win() {
+		let mut ctx = SimContext::default();
+		let mut app = hangman(&mut ctx);
+
+		assert_eq!(send(&mut app, &mut ctx, "O"), "O?? 6");
+		assert_eq!(send(&mut app, &mut ctx, "E"), "O?? 5");
+		assert_eq!(send(&mut app, &mut ctx, "E"), "O?? 5");
+		assert_eq!(send(&mut app, &mut ctx, "U"), "OU? 5");
+		assert!(send(&mut app, &mut ctx, "T").starts_with("WIN OUT = "));
+	}
+
+	#[test]
+	fn hangman_word_guess() {
+		let mut ctx = SimContext::default();
+		let mut app = hangman(&mut ctx);
+
+		assert_eq!(send(&mut app, &mut ctx, "OAT"), "??? 5");
+		assert!(send(&mut app, &mut ctx, "OUT").starts_with("WIN OUT = "));
+	}
+
+	#[test]
+	fn hangman_loss() {
+		let mut ctx = SimContext::default();
+		let mut app = hangman(&mut ctx);
+
+		for (letter, lives) in ["A", "B", "C", "D", "E"].iter().zip((1..6).rev()) {
+			assert_eq!(send(&mut app, &mut ctx, letter), format!("??? {lives}"));
+		}
+
+		assert!(send(&mut app, &mut ctx, "F").starts_with("LOST OUT = "));
+	}
+
+	#[test]
+	fn number_guessing() {
+		let reply = guess_number(&mut SimContext::default());
+
+		assert!(reply.starts_with('R'));
+		assert!(reply.ends_with(&format!("TRIES = NR 1 {MAX_NUMBER}")));
+
+		// the same seed picks the same number
+		assert_eq!(guess_number(&mut SimContext::default()), reply);
+	}
+}
@@ -0,0 +1,154 @@
+use super::{idk, words::align, CwApp};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const KOCH_KEY: &str = "koch";
+
+/// characters in the order they are learned with the Koch method
+const KOCH_ORDER: &str = "KMURESNAPTLWI.JZ=FOY,VG5/Q92H38B?47C1D60X";
+
+const GROUPS: usize = 5;
+const GROUP_LEN: usize = 5;
+
+/// accuracy in percent above which the next character is unlocked
+const UNLOCK_ACCURACY: u32 = 90;
+
+/// number of times a character is sent after which its stats are halved,
+/// so they follow the recent copy rather than the whole history
+const STATS_WINDOW: u32 = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KochProgress {
+	/// number of unlocked characters
+	level: usize,
+	/// how often each character was sent and copied correctly
+	stats: BTreeMap<char, (u32, u32)>,
+}
+
+impl KochProgress {
+	/// whether every unlocked character was copied well enough to learn the next one
+	///
+	/// => characters that were never sent block the unlock
+	fn is_unlocking(&self) -> bool {
+		self.level < KOCH_ORDER.len()
+			&& KOCH_ORDER.chars().take(self.level).all(|c| {
+				self.stats
+					.get(&c)
+					.and_then(|(sent, correct)| (correct * 100).checked_div(*sent))
+					.is_some_and(|accuracy| accuracy > UNLOCK_ACCURACY)
+			})
+	}
+}
+
+impl Default for KochProgress {
+	fn default() -> Self {
+		Self {
+			level: 2,
+			stats: BTreeMap::new(),
+		}
+	}
+}
+
+/// Koch method trainer
+///
+/// sends random groups of the unlocked characters, which the operator keys back,
+/// then replies with the accuracy in percent, followed by the next groups
+///
+/// => once the accuracy is above 90%, both for the groups and for every unlocked character,
+/// the next character is unlocked and sent as `NEW K`
+#[derive(Default)]
+pub struct Koch {
+	groups: Vec<String>,
+}
+
+impl CwApp for Koch {
+	fn name(&self) -> &'static str {
+		"KO"
+	}
+
+	fn description(&self) -> &'static str {
+		"KOCH METHOD TRAINER"
+	}
+
+	fn usage(&self) -> &'static str {
+		"KEY BACK THE GROUPS TO GET YOUR ACCURACY AND NEW GROUPS"
+	}
+
+	fn enter(&mut self, ctx: &mut dyn CwContext, _args: CwString) -> CwString {
+		let groups = self.next_groups(ctx);
+		CwString::from(groups.as_str())
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		if input.0.is_empty() {
+			return Default::default();
+		}
+
+		let mut progress = ctx.load::<KochProgress>(KOCH_KEY).unwrap_or_default();
+
+		let copy = String::from(&input);
+		let copied_groups = copy.split_whitespace().collect::<Vec<_>>();
+
+		let (mut sent, mut correct) = (0u32, 0u32);
+
+		// grade the aligned characters of every group, missing characters count as mistakes
+		for (index, group) in self.groups.iter().enumerate() {
+			let copied_group = copied_groups.get(index).copied().unwrap_or_default();
+
+			for (sent_char, copied_char) in align(group, copied_group) {
+				let Some(sent_char) = sent_char else {
+					continue;
+				};
+
+				let is_correct = copied_char == Some(sent_char);
+				let stats = progress.stats.entry(sent_char).or_default();
+
+				stats.0 += 1;
+				sent += 1;
+
+				if is_correct {
+					stats.1 += 1;
+					correct += 1;
+				}
+
+				if stats.0 > STATS_WINDOW {
+					*stats = (stats.0 / 2, stats.1 / 2);
+				}
+			}
+		}
+
+		let accuracy = (correct * 100).checked_div(sent).unwrap_or_default();
+		let mut reply = format!("{accuracy}");
+
+		if accuracy > UNLOCK_ACCURACY && progress.is_unlocking() {
+			progress.level += 1;
+			let new_char = KOCH_ORDER.chars().nth(progress.level - 1).unwrap();
+			reply += &format!(" NEW {new_char}");
+		}
+
+		if ctx.store(KOCH_KEY, &progress).is_err() {
+			return idk();
+		}
+
+		reply += &format!(" = {}", self.next_groups(ctx));
+		CwString::from(reply.as_str())
+	}
+}
+
+impl Koch {
+	fn next_groups(&mut self, ctx: &mut dyn CwContext) -> String {
+		let progress = ctx.load::<KochProgress>(KOCH_KEY).unwrap_or_default();
+		let chars = KOCH_ORDER.chars().take(progress.level).collect::<Vec<_>>();
+
+		self.groups = (0..GROUPS)
+			.map(|_| {
+				(0..GROUP_LEN)
+					.map(|_| chars[ctx.random_index(chars.len())])
+					.collect()
+			})
+			.collect();
+
+		self.groups.join(" ")
+	}
+}
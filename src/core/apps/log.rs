@@ -0,0 +1,142 @@
+use super::{
+	idk,
+	words::{is_callsign, is_rst},
+	CwApp,
+};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const LOG_KEY: &str = "log";
+
+/// number of entries sent by `L` if no number is given
+const RECENT: usize = 1;
+
+/// a logged contact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+	/// ms since the UNIX epoch at which the contact was logged
+	pub time: u64,
+	pub call: String,
+	/// report given to the station, may be empty
+	pub rst_sent: String,
+	/// report received from the station, may be empty
+	pub rst_rcvd: String,
+	pub notes: String,
+}
+
+/// station logbook
+///
+/// ## Commands
+/// - `L`: sends the latest entry
+/// - `L 3`: sends the latest three entries
+/// - `D 2`: deletes the second entry
+/// - anything else is logged as a new contact: the call, optionally the sent and received RST,
+///   then any notes, e.g. `DL1ABC 599 579 FB SIG`
+///
+/// => entries are sent with their number and UTC time, e.g. `2 DL1ABC 1435Z 599 579 FB SIG`
+#[derive(Default)]
+pub struct Log;
+
+impl CwApp for Log {
+	fn name(&self) -> &'static str {
+		"LG"
+	}
+
+	fn description(&self) -> &'static str {
+		"LOGBOOK"
+	}
+
+	fn usage(&self) -> &'static str {
+		"KEY CALL RST SENT RST RCVD NOTES TO LOG = L LATEST = L 3 LATEST 3 = D 2 DELETE"
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let words = input.split_whitespace().collect::<Vec<_>>();
+		let mut log = ctx.load::<Vec<LogEntry>>(LOG_KEY).unwrap_or_default();
+
+		let reply = match words.as_slice() {
+			[] => return Default::default(),
+			["L"] => Self::recent(&log, RECENT),
+			["L", count] => match count.parse::<usize>() {
+				Ok(count) if count > 0 => Self::recent(&log, count),
+				_ => return idk(),
+			},
+			["D", index] => {
+				let index = match index.parse::<usize>() {
+					Ok(index) if (1..=log.len()).contains(&index) => index,
+					_ => return idk(),
+				};
+
+				log.remove(index - 1);
+				if ctx.store(LOG_KEY, &log).is_err() {
+					return idk();
+				}
+				"R".to_string()
+			}
+			[call, rest @ ..] => {
+				if !is_callsign(call) {
+					return idk();
+				}
+
+				let rst_count = rest.iter().take(2).take_while(|word| is_rst(word)).count();
+				let (rst, notes) = rest.split_at(rst_count);
+
+				log.push(LogEntry {
+					time: ctx.time(),
+					call: call.to_string(),
+					rst_sent: Self::rst(rst.first()),
+					rst_rcvd: Self::rst(rst.get(1)),
+					notes: notes.join(" "),
+				});
+				if ctx.store(LOG_KEY, &log).is_err() {
+					return idk();
+				}
+				format!("R {}", log.len())
+			}
+		};
+
+		CwString::from(reply.as_str())
+	}
+}
+
+impl Log {
+	/// the latest entries, oldest first
+	fn recent(log: &[LogEntry], count: usize) -> String {
+		if log.is_empty() {
+			return "NIL".to_string();
+		}
+
+		let start = log.len().saturating_sub(count);
+
+		log.iter()
+			.enumerate()
+			.skip(start)
+			.map(|(index, entry)| format!("{} {}", index + 1, Self::format(entry)))
+			.collect::<Vec<_>>()
+			.join(" = ")
+	}
+
+	/// e.g. `DL1ABC 1435Z 599 579 FB SIG`
+	fn format(entry: &LogEntry) -> String {
+		let DateTime { hour, minute, .. } = DateTime::from_epoch_ms(entry.time, 0);
+
+		[
+			entry.call.clone(),
+			format!("{hour:02}{minute:02}Z"),
+			entry.rst_sent.clone(),
+			entry.rst_rcvd.clone(),
+			entry.notes.clone(),
+		]
+		.iter()
+		.filter(|field| !field.is_empty())
+		.cloned()
+		.collect::<Vec<_>>()
+		.join(" ")
+	}
+
+	/// cut numbers are logged as digits, e.g. `5NN` as `599`
+	fn rst(word: Option<&&str>) -> String {
+		word.map(|word| word.replace('N', "9")).unwrap_or_default()
+	}
+}
@@ -1,34 +1,224 @@
+mod calculator;
+mod clock;
+mod contest;
+mod copy;
+mod dictionary;
+mod games;
+mod koch;
+mod log;
+mod notes;
+mod qso;
+mod settings;
+mod timers;
+mod words;
+
+pub use calculator::*;
+pub use clock::*;
+pub use contest::*;
+pub use copy::*;
+pub use dictionary::*;
+pub use games::*;
+pub use koch::*;
+pub use log::*;
+pub use notes::*;
+pub use qso::*;
+pub use settings::*;
+pub use timers::*;
+
 use crate::prelude::*;
 
-#[derive(Default)]
+/// an app that can be registered in the `AppLauncher`
+pub trait CwApp {
+	/// short code the app is launched with, e.g. `EC`
+	fn name(&self) -> &'static str;
+
+	/// what the app does, in a few words
+	fn description(&self) -> &'static str;
+
+	/// how to use the app, sent by the help
+	fn usage(&self) -> &'static str {
+		self.description()
+	}
+
+	/// called when the app is launched, with the arguments that were keyed after its name
+	///
+	/// => replies with the app name by default, or passes the arguments on to `tick`
+	fn enter(&mut self, ctx: &mut dyn CwContext, args: CwString) -> CwString {
+		match args.0.is_empty() {
+			true => CwString::from(self.name()),
+			false => self.tick(ctx, args),
+		}
+	}
+
+	/// called with every input while the app is running
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString;
+
+	/// receives the input decoded so far, before it is passed to `tick`
+	fn preview(&mut self, _ctx: &mut dyn CwContext, _input: &CwString) {}
+
+	/// voices that are mixed in after the reply to `enter` or `tick`, e.g. other stations
+	///
+	/// => their delay counts from the end of the reply
+	fn mix(&mut self, _ctx: &mut dyn CwContext) -> Vec<Voice> {
+		vec![]
+	}
+
+	/// called when the app is left
+	fn exit(&mut self, _ctx: &mut dyn CwContext) {}
+}
+
+/// launches the registered apps and navigates between them
+///
+/// ## Navigation
+/// - `EC`: launches an app by its name, optionally followed by arguments
+/// - `^EC` ([CT] + name): launches an app from within another app, unless it is already running
+/// - `#` ([VA]): leaves the current app and returns to its caller
+/// - `X`: quits, when no app is running
+/// - `?`: lists the names and descriptions of all apps
+/// - `? EC`: sends the usage of an app
+///
+/// => after leaving an app, the name of the app the operator returned to is sent,
+/// or `CWOS` when they are back in the launcher
 pub struct AppLauncher {
-	selected_app: Option<CwString>,
+	apps: Vec<Box<dyn CwApp>>,
+	app_stack: Vec<usize>,
 }
 
-// todo: streamline AppLauncher
-// todo: add clock app
-// todo: add timer scheduler
-impl CwController<CwString, CwString> for AppLauncher {
-	fn tick(&mut self, ctx: &mut impl CwContext, input: CwString) -> CwString {
+impl Default for AppLauncher {
+	fn default() -> Self {
+		let mut launcher = Self::new();
+		launcher.register(Echo);
+		launcher.register(Clock);
+		launcher.register(Timers);
+		launcher.register(Settings);
+		launcher.register(Koch::default());
+		launcher.register(CopyPractice::default());
+		launcher.register(Notes);
+		launcher.register(Calculator);
+		launcher.register(Qso::default());
+		launcher.register(Contest::default());
+		launcher.register(Log);
+		launcher.register(Dictionary);
+		launcher.register(Games::default());
+		launcher
+	}
+}
+
+impl CwController<CwString, Vec<Voice>> for AppLauncher {
+	fn tick(&mut self, ctx: &mut impl CwContext, input: CwString) -> Vec<Voice> {
+		let reply = Voice::from(self.reply(ctx, input));
+		let reply_ms = reply.duration_ms(ctx.config().output.signal);
+
+		let mut voices = vec![reply];
+
+		if let Some(&index) = self.app_stack.last() {
+			let mixed = self.apps[index].mix(ctx).into_iter().map(|mut voice| {
+				voice.delay_ms += reply_ms;
+				voice
+			});
+
+			voices.extend(mixed);
+		}
+
+		voices
+	}
+
+	fn preview(&mut self, ctx: &mut impl CwContext, input: &CwString) {
+		if let Some(&index) = self.app_stack.last() {
+			self.apps[index].preview(ctx, input);
+		}
+	}
+}
+
+impl AppLauncher {
+	/// creates a launcher without any apps
+	pub fn new() -> Self {
+		Self {
+			apps: vec![],
+			app_stack: vec![],
+		}
+	}
+
+	pub fn register(&mut self, app: impl CwApp + 'static) {
+		self.apps.push(Box::new(app));
+	}
+
+	/// all registered apps, in the order they were registered
+	pub fn apps(&self) -> impl Iterator<Item = &dyn CwApp> {
+		self.apps.iter().map(|app| app.as_ref())
+	}
+
+	fn reply(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
 		use CwSymbol::*;
 
-		match &self.selected_app {
-			Some(app_name) => match app_name.0.as_slice() {
-				[E, C] => Echo.tick(ctx, input),
-				_ => idk(),
-			},
-			None => match input.normalized().0.as_slice() {
-				app_name @ [E, C] => {
-					self.selected_app = Some(CwString(app_name.to_vec()));
-					CwString(app_name.to_vec())
-				}
-				[X] | [End] => {
-					ctx.quit();
-					Default::default()
-				}
-				[] => Default::default(),
-				_ => idk(),
-			},
+		let input = input.normalized();
+
+		match (input.0.as_slice(), self.app_stack.last()) {
+			([Start, app @ ..], _) => {
+				let app = CwString(app.to_vec()).normalized();
+				self.launch(ctx, &app).unwrap_or_else(idk)
+			}
+			([End], _) => self.leave(ctx),
+			(_, Some(&index)) => self.apps[index].tick(ctx, input),
+			([X], None) => {
+				ctx.quit();
+				Default::default()
+			}
+			([], None) => Default::default(),
+			(_, None) => self.launch(ctx, &input).unwrap_or_else(idk),
+		}
+	}
+
+	fn find(&self, app_name: &CwString) -> Option<usize> {
+		self.apps
+			.iter()
+			.position(|app| CwString::from(app.name()) == *app_name)
+	}
+
+	/// apps may be launched with arguments, e.g. `TM 5M`
+	fn launch(&mut self, ctx: &mut dyn CwContext, input: &CwString) -> Option<CwString> {
+		let (app_name, args) = input.split_first_word();
+
+		if app_name.0 == [CwSymbol::Question] {
+			return self.help(&args);
+		}
+
+		let index = self.find(&app_name)?;
+
+		// every app has a single instance, so launching it again would reset its caller
+		if self.app_stack.contains(&index) {
+			return None;
+		}
+
+		self.app_stack.push(index);
+		Some(self.apps[index].enter(ctx, args))
+	}
+
+	/// e.g. `EC REPEATS ANY INPUT = CK TIME AND DATE` or `EC KEY ANYTHING TO HEAR IT BACK`
+	fn help(&self, app_name: &CwString) -> Option<CwString> {
+		let help = match app_name.0.is_empty() {
+			true => self
+				.apps()
+				.map(|app| format!("{} {}", app.name(), app.description()))
+				.collect::<Vec<_>>()
+				.join(" = "),
+			false => {
+				let app = &self.apps[self.find(app_name)?];
+				format!("{} {}", app.name(), app.usage())
+			}
+		};
+
+		Some(CwString::from(help.as_str()))
+	}
+
+	fn leave(&mut self, ctx: &mut dyn CwContext) -> CwString {
+		if let Some(index) = self.app_stack.pop() {
+			self.apps[index].exit(ctx);
+		}
+
+		match self.app_stack.last() {
+			Some(&index) => CwString::from(self.apps[index].name()),
+			None => CwString::from("CWOS"),
 		}
 	}
 }
@@ -40,8 +230,20 @@ fn idk() -> CwString {
 #[derive(Default)]
 pub struct Echo;
 
-impl CwController<CwString, CwString> for Echo {
-	fn tick(&mut self, _ctx: &mut impl CwContext, input: CwString) -> CwString {
+impl CwApp for Echo {
+	fn name(&self) -> &'static str {
+		"EC"
+	}
+
+	fn description(&self) -> &'static str {
+		"REPEATS ANY INPUT"
+	}
+
+	fn usage(&self) -> &'static str {
+		"KEY ANYTHING TO HEAR IT BACK"
+	}
+
+	fn tick(&mut self, _ctx: &mut dyn CwContext, input: CwString) -> CwString {
 		input
 	}
 }
@@ -0,0 +1,85 @@
+use super::{idk, CwApp};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const NOTES_KEY: &str = "notes";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Note {
+	/// ms since the UNIX epoch at which the note was taken
+	time: u64,
+	text: String,
+}
+
+/// takes short notes
+///
+/// ## Commands
+/// - `L`: sends the latest note
+/// - `A`: sends all notes
+/// - `D 2`: deletes the second note
+/// - anything else is saved as a new note
+///
+/// => notes are sent with their number, e.g. `2 BUY BATTERIES`
+#[derive(Default)]
+pub struct Notes;
+
+impl CwApp for Notes {
+	fn name(&self) -> &'static str {
+		"NT"
+	}
+
+	fn description(&self) -> &'static str {
+		"NOTES"
+	}
+
+	fn usage(&self) -> &'static str {
+		"KEY A NOTE TO SAVE IT = L LATEST = A ALL = D 2 DELETE"
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let words = input.split_whitespace().collect::<Vec<_>>();
+		let mut notes = ctx.load::<Vec<Note>>(NOTES_KEY).unwrap_or_default();
+
+		let reply = match words.as_slice() {
+			[] => return Default::default(),
+			["L"] => match notes.last() {
+				Some(note) => format!("{} {}", notes.len(), note.text),
+				None => "NIL".to_string(),
+			},
+			["A"] => match notes.is_empty() {
+				true => "NIL".to_string(),
+				false => notes
+					.iter()
+					.enumerate()
+					.map(|(index, note)| format!("{} {}", index + 1, note.text))
+					.collect::<Vec<_>>()
+					.join(" = "),
+			},
+			["D", index] => {
+				let index = match index.parse::<usize>() {
+					Ok(index) if (1..=notes.len()).contains(&index) => index,
+					_ => return idk(),
+				};
+
+				notes.remove(index - 1);
+				if ctx.store(NOTES_KEY, &notes).is_err() {
+					return idk();
+				}
+				"R".to_string()
+			}
+			words => {
+				notes.push(Note {
+					time: ctx.time(),
+					text: words.join(" "),
+				});
+				if ctx.store(NOTES_KEY, &notes).is_err() {
+					return idk();
+				}
+				format!("R {}", notes.len())
+			}
+		};
+
+		CwString::from(reply.as_str())
+	}
+}
@@ -0,0 +1,209 @@
+use super::{
+	words::{is_callsign, is_rst, random_callsign},
+	CwApp,
+};
+use crate::prelude::*;
+
+const NAMES: [&str; 10] = [
+	"JOHN", "HANS", "PETE", "ANNA", "BOB", "JIM", "MIKE", "TOM", "EVA", "PAUL",
+];
+
+const QTHS: [&str; 10] = [
+	"BERLIN", "PARIS", "OHIO", "TEXAS", "TOKYO", "MUNICH", "LONDON", "ROME", "OSLO", "PRAGUE",
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum QsoState {
+	/// the station is calling CQ
+	#[default]
+	Calling,
+	/// the operator answered and got the station's report
+	Answered,
+	/// the operator sent their report and got the station's 73
+	Exchanged,
+}
+
+/// plays a station to practise a basic contact with
+///
+/// ## Contact
+/// 1. the station calls CQ, e.g. `CQ CQ DE DL1ABC DL1ABC K`
+/// 2. answer with both calls, e.g. `DL1ABC DE W1XYZ K`
+/// 3. send your report, e.g. `R TNX UR RST 599 NAME BOB QTH OHIO (`
+/// 4. end the contact, e.g. `73 #`
+///
+/// the station hands over with `(` ([KN]) during the contact,
+/// and ends its last over with `+` ([AR]) and `#` ([SK])
+///
+/// => protocol mistakes are flagged with `ERR`, e.g. `ERR DE` for a missing `DE`,
+/// missing information is asked for with `?`, e.g. `RST?`
+#[derive(Default)]
+pub struct Qso {
+	state: QsoState,
+	call: String,
+	name: String,
+	qth: String,
+	operator_call: String,
+}
+
+impl CwApp for Qso {
+	fn name(&self) -> &'static str {
+		"QS"
+	}
+
+	fn description(&self) -> &'static str {
+		"QSO SIMULATOR"
+	}
+
+	fn usage(&self) -> &'static str {
+		"ANSWER THE CQ WITH CALL DE YOURCALL K = SEND RST NAME QTH ( = END WITH 73 #"
+	}
+
+	fn enter(&mut self, ctx: &mut dyn CwContext, _args: CwString) -> CwString {
+		CwString::from(self.call_cq(ctx).as_str())
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let words = input.split_whitespace().collect::<Vec<_>>();
+
+		if words.is_empty() {
+			return Default::default();
+		}
+
+		let reply = match self.state {
+			QsoState::Calling => self.answer(ctx, &words),
+			QsoState::Answered => self.exchange(&words),
+			QsoState::Exchanged => self.end(ctx, &words),
+		};
+
+		CwString::from(reply.as_str())
+	}
+}
+
+impl Qso {
+	fn call_cq(&mut self, ctx: &mut dyn CwContext) -> String {
+		self.state = QsoState::Calling;
+		self.call = random_callsign(ctx);
+		self.name = NAMES[ctx.random_index(NAMES.len())].to_string();
+		self.qth = QTHS[ctx.random_index(QTHS.len())].to_string();
+
+		let call = &self.call;
+		format!("CQ CQ DE {call} {call} K")
+	}
+
+	/// the operator answers the CQ with their call
+	fn answer(&mut self, ctx: &mut dyn CwContext, words: &[&str]) -> String {
+		let operator_call = match Self::operator_call(words, &self.call) {
+			Some(operator_call) => operator_call,
+			None => return "QRZ?".to_string(),
+		};
+
+		let mut mistakes = vec![];
+		if !words.contains(&self.call.as_str()) {
+			mistakes.push("CALL");
+		}
+		if !words.contains(&"DE") {
+			mistakes.push("DE");
+		}
+		if !Self::hands_over(words) {
+			mistakes.push("K");
+		}
+
+		self.operator_call = operator_call.to_string();
+		self.state = QsoState::Answered;
+
+		let rst = format!("5{}9", 5 + ctx.random_index(5));
+		let (call, name, qth) = (&self.call, &self.name, &self.qth);
+
+		let reply = format!(
+			"{operator_call} DE {call} GM TNX FER CALL UR RST {rst} {rst} NAME {name} {name} \
+			QTH {qth} {qth} HW? {operator_call} DE {call} ("
+		);
+
+		Self::flag(mistakes, reply)
+	}
+
+	/// the operator sends their report
+	fn exchange(&mut self, words: &[&str]) -> String {
+		let rst = words.iter().find(|word| is_rst(word));
+		let name = Self::value_after(words, &["NAME", "OP"]);
+		let qth = Self::value_after(words, &["QTH"]);
+
+		let missing = [
+			("RST", rst.is_some()),
+			("NAME", name.is_some()),
+			("QTH", qth.is_some()),
+		]
+		.iter()
+		.filter(|(_, is_sent)| !is_sent)
+		.map(|(info, _)| format!("{info}?"))
+		.collect::<Vec<_>>();
+
+		if !missing.is_empty() {
+			return format!("{} (", missing.join(" "));
+		}
+
+		let mut mistakes = vec![];
+		if !Self::hands_over(words) {
+			mistakes.push("K");
+		}
+
+		self.state = QsoState::Exchanged;
+
+		let (call, operator_call) = (&self.call, &self.operator_call);
+		let name = name.unwrap_or_default();
+
+		let reply = format!("R R TNX {name} FER QSO 73 {operator_call} DE {call} + #");
+		Self::flag(mistakes, reply)
+	}
+
+	/// the operator ends the contact, then the next station calls CQ
+	fn end(&mut self, ctx: &mut dyn CwContext, words: &[&str]) -> String {
+		let mut mistakes = vec![];
+		if !words.iter().any(|word| ["73", "TU"].contains(word)) {
+			mistakes.push("73");
+		}
+		if !words.iter().any(|word| word.ends_with('#')) {
+			mistakes.push("#");
+		}
+
+		let reply = format!("TU E E = {}", self.call_cq(ctx));
+		Self::flag(mistakes, reply)
+	}
+
+	/// the operator's call is the one after `DE`, or any other call that isn't the station's
+	fn operator_call<'a>(words: &[&'a str], call: &str) -> Option<&'a str> {
+		let after_de = words
+			.iter()
+			.position(|word| *word == "DE")
+			.and_then(|index| words.get(index + 1));
+
+		after_de
+			.or_else(|| words.iter().find(|word| *word != &call))
+			.copied()
+			.filter(|word| is_callsign(word) && *word != call)
+	}
+
+	fn value_after<'a>(words: &[&'a str], keys: &[&str]) -> Option<&'a str> {
+		let index = words.iter().position(|word| keys.contains(word))?;
+
+		words[index + 1..]
+			.iter()
+			.find(|word| **word != "IS")
+			.copied()
+	}
+
+	/// whether the transmission ends with `K`, `[KN]` or `[AR]`
+	fn hands_over(words: &[&str]) -> bool {
+		words
+			.last()
+			.is_some_and(|word| ["K", "KN", "AR"].contains(word) || word.ends_with(['(', '+']))
+	}
+
+	fn flag(mistakes: Vec<&str>, reply: String) -> String {
+		match mistakes.is_empty() {
+			true => reply,
+			false => format!("ERR {} = {reply}", mistakes.join(" ")),
+		}
+	}
+}
@@ -0,0 +1,97 @@
+use super::{idk, CwApp};
+use crate::{prelude::*, wpm};
+
+/// queries and changes the signal config, which is applied and saved right away
+///
+/// ## Commands
+/// - `WPM`, `FW`, `FQ`: sends the speed, Farnsworth speed or tone frequency
+/// - `WPM 25`: changes the value
+/// - `I WPM 25` / `O WPM 25`: refers to the input or output config, the output is the default
+#[derive(Default)]
+pub struct Settings;
+
+impl CwApp for Settings {
+	fn name(&self) -> &'static str {
+		"ST"
+	}
+
+	fn description(&self) -> &'static str {
+		"SPEED AND TONE SETTINGS"
+	}
+
+	fn usage(&self) -> &'static str {
+		"WPM FW OR FQ TO QUERY = WPM 25 TO CHANGE = I WPM 25 FOR INPUT"
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let words = input.split_whitespace().collect::<Vec<_>>();
+
+		if words.is_empty() {
+			return Default::default();
+		}
+
+		match Self::command(ctx, &words) {
+			Some(reply) => CwString::from(reply.as_str()),
+			None => idk(),
+		}
+	}
+}
+
+impl Settings {
+	fn command(ctx: &mut dyn CwContext, words: &[&str]) -> Option<String> {
+		let (is_input, words) = match words {
+			["I", words @ ..] => (true, words),
+			["O", words @ ..] => (false, words),
+			words => (false, words),
+		};
+
+		let mut config = ctx.config();
+		let signal = match is_input {
+			true => &mut config.input.signal,
+			false => &mut config.output.signal,
+		};
+
+		match words {
+			[key] => {
+				let value = Self::get(signal, key)?;
+				Some(format!("{key} {value}"))
+			}
+			[key, value] => {
+				let value = value.parse().ok()?;
+				Self::set(signal, key, value)?;
+				ctx.set_config(config).ok()?;
+				Some(format!("R {key} {value}"))
+			}
+			_ => None,
+		}
+	}
+
+	fn get(signal: &SignalConfig, key: &str) -> Option<u32> {
+		match key {
+			"WPM" => Some(wpm!(signal.unit_ms)),
+			"FW" => Some(wpm!(signal.fw_ms)),
+			"FQ" => Some(signal.freq),
+			_ => None,
+		}
+	}
+
+	fn set(signal: &mut SignalConfig, key: &str, value: u32) -> Option<()> {
+		match key {
+			"WPM" if (5..=60).contains(&value) => {
+				signal.unit_ms = wpm!(value);
+				// the Farnsworth speed may not be faster than the speed
+				signal.fw_ms = signal.fw_ms.max(signal.unit_ms);
+			}
+			"FW" if (5..=60).contains(&value) && value <= wpm!(signal.unit_ms) => {
+				signal.fw_ms = wpm!(value);
+			}
+			"FQ" if (300..=1500).contains(&value) => {
+				signal.freq = value;
+			}
+			_ => return None,
+		}
+
+		Some(())
+	}
+}
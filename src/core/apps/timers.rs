@@ -0,0 +1,187 @@
+use super::{idk, CwApp};
+use crate::prelude::*;
+
+/// sets countdowns and alarms, which are sent by the `Scheduler` once they are due
+///
+/// ## Commands
+/// - `5M`, `1H30M`, `90S`: countdown, plain numbers are minutes
+/// - `A 0730`: alarm at the next 07:30 local time
+/// - `L`: lists the pending timers with their remaining time
+/// - `C 1`: cancels the first pending timer
+#[derive(Default)]
+pub struct Timers;
+
+impl CwApp for Timers {
+	fn name(&self) -> &'static str {
+		"TM"
+	}
+
+	fn description(&self) -> &'static str {
+		"COUNTDOWNS AND ALARMS"
+	}
+
+	fn usage(&self) -> &'static str {
+		"5M OR 1H30M COUNTDOWN = A 0730 ALARM = L LIST = C 1 CANCEL"
+	}
+
+	fn tick(&mut self, ctx: &mut dyn CwContext, input: CwString) -> CwString {
+		let input = String::from(&input);
+		let words = input.split_whitespace().collect::<Vec<_>>();
+
+		let reply = match words.as_slice() {
+			[] => return Default::default(),
+			["L"] => Some(Self::list(ctx)),
+			["A", time] => Self::alarm(ctx, time),
+			["C", index] => Self::cancel(ctx, index),
+			[duration] => Self::countdown(ctx, duration),
+			_ => None,
+		};
+
+		match reply {
+			Some(reply) => CwString::from(reply.as_str()),
+			None => idk(),
+		}
+	}
+}
+
+impl Timers {
+	fn countdown(ctx: &mut dyn CwContext, duration: &str) -> Option<String> {
+		let duration_ms = parse_duration(duration)?;
+		let due = ctx.time().checked_add(duration_ms)?;
+
+		Scheduler::schedule(
+			ctx,
+			Timer {
+				due,
+				message: format!("TIMER {duration}"),
+			},
+		)
+		.ok()?;
+
+		Some(format!("R {duration}"))
+	}
+
+	fn alarm(ctx: &mut dyn CwContext, time: &str) -> Option<String> {
+		let (hour, minute) = match time.as_bytes() {
+			[_, _, _, _] => (
+				time[..2].parse::<u64>().ok()?,
+				time[2..].parse::<u64>().ok()?,
+			),
+			_ => return None,
+		};
+
+		if hour >= 24 || minute >= 60 {
+			return None;
+		}
+
+		let now = ctx.time();
+		let local =
+			DateTime::from_epoch_ms(now, ctx.config().clock.utc_offset_min.unwrap_or_default());
+
+		const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+		let local_ms = ((local.hour * 60 + local.minute) * 60 + local.second) as u64 * 1000;
+		let alarm_ms = (hour * 60 + minute) * 60 * 1000;
+
+		// the next time the clock shows the alarm time, at least a minute from now
+		let mut delta_ms = (alarm_ms + DAY_MS - local_ms) % DAY_MS;
+		if delta_ms < 60 * 1000 {
+			delta_ms += DAY_MS;
+		}
+
+		Scheduler::schedule(
+			ctx,
+			Timer {
+				due: now + delta_ms,
+				message: format!("ALARM {time}"),
+			},
+		)
+		.ok()?;
+
+		Some(format!("R {time}"))
+	}
+
+	/// e.g. `1 4M30S 2 8H`
+	fn list(ctx: &mut dyn CwContext) -> String {
+		let now = ctx.time();
+		let timers = Scheduler::timers(ctx);
+
+		if timers.is_empty() {
+			return "NIL".to_string();
+		}
+
+		timers
+			.iter()
+			.enumerate()
+			.map(|(index, timer)| {
+				let remaining = format_duration(timer.due.saturating_sub(now));
+				format!("{} {remaining}", index + 1)
+			})
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+
+	fn cancel(ctx: &mut dyn CwContext, index: &str) -> Option<String> {
+		let index = index.parse::<usize>().ok()?.checked_sub(1)?;
+		Scheduler::cancel(ctx, index)?;
+		Some("R".to_string())
+	}
+}
+
+/// parses durations like `1H30M` or `90S` into ms
+///
+/// => trailing numbers without a unit are minutes
+fn parse_duration(duration: &str) -> Option<u64> {
+	let mut total_secs = 0;
+	let mut number: Option<u64> = None;
+
+	for c in duration.chars() {
+		if let Some(digit) = c.to_digit(10) {
+			number = Some(
+				number
+					.unwrap_or(0)
+					.checked_mul(10)?
+					.checked_add(digit as u64)?,
+			);
+			continue;
+		}
+
+		let unit_secs = match c {
+			'H' => 60 * 60,
+			'M' => 60,
+			'S' => 1,
+			_ => return None,
+		};
+
+		total_secs = number
+			.take()?
+			.checked_mul(unit_secs)?
+			.checked_add(total_secs)?;
+	}
+
+	total_secs = number
+		.unwrap_or(0)
+		.checked_mul(60)?
+		.checked_add(total_secs)?;
+
+	match total_secs {
+		0 => None,
+		_ => total_secs.checked_mul(1000),
+	}
+}
+
+/// formats ms like `1H30M` or `45S`, rounded up to full seconds
+fn format_duration(duration_ms: u64) -> String {
+	let secs = duration_ms.div_ceil(1000);
+	let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+	let parts = [(hours, "H"), (minutes, "M"), (secs, "S")]
+		.iter()
+		.filter(|(value, _)| *value > 0)
+		.map(|(value, unit)| format!("{value}{unit}"))
+		.collect::<String>();
+
+	match parts.is_empty() {
+		true => "0S".to_string(),
+		false => parts,
+	}
+}
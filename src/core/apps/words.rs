@@ -0,0 +1,107 @@
+use crate::prelude::*;
+
+const WORDS: [&str; 40] = [
+	"THE", "AND", "FOR", "ARE", "BUT", "NOT", "YOU", "ALL", "ANY", "CAN", "HAD", "HER", "WAS",
+	"ONE", "OUR", "OUT", "DAY", "GET", "HAS", "HIM", "HIS", "HOW", "MAN", "NEW", "NOW", "OLD",
+	"SEE", "TWO", "WAY", "WHO", "RIG", "ANT", "WX", "NAME", "QTH", "RST", "TEST", "CALL", "GOOD",
+	"RADIO",
+];
+
+const PREFIXES: [&str; 20] = [
+	"K", "W", "N", "AA", "KB", "DL", "DK", "G", "M", "F", "I", "JA", "VK", "VE", "EA", "ON", "PA",
+	"OH", "SM", "OK",
+];
+
+const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn random_char(ctx: &mut dyn CwContext, chars: &str) -> char {
+	let index = ctx.random_index(chars.len());
+	chars.chars().nth(index).unwrap()
+}
+
+pub fn random_word(ctx: &mut dyn CwContext) -> String {
+	WORDS[ctx.random_index(WORDS.len())].to_string()
+}
+
+/// e.g. `DL1ABC` or `K5XY`
+pub fn random_callsign(ctx: &mut dyn CwContext) -> String {
+	let prefix = PREFIXES[ctx.random_index(PREFIXES.len())];
+	let digit = ctx.random_index(10);
+	let suffix = (0..1 + ctx.random_index(3))
+		.map(|_| random_char(ctx, LETTERS))
+		.collect::<String>();
+
+	format!("{prefix}{digit}{suffix}")
+}
+
+/// a number with one to five digits
+pub fn random_number(ctx: &mut dyn CwContext) -> String {
+	let digits = 1 + ctx.random_index(5) as u32;
+	ctx.random_index(10usize.pow(digits)).to_string()
+}
+
+/// e.g. `DL1ABC`, `K5XY` or `DL/G4ABC`
+pub fn is_callsign(word: &str) -> bool {
+	(3..=10).contains(&word.len())
+		&& word.chars().all(|c| c.is_ascii_alphanumeric() || c == '/')
+		&& word.chars().any(|c| c.is_ascii_digit())
+		&& word.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// e.g. `599` or `5NN`
+pub fn is_rst(word: &str) -> bool {
+	let word = word.replace('N', "9");
+	word.len() == 3
+		&& word.starts_with(['1', '2', '3', '4', '5'])
+		&& word.chars().all(|c| ('1'..='9').contains(&c))
+}
+
+/// pairs the sent and copied characters with the fewest edits (Levenshtein distance),
+/// `None` on the sent side is an extra copied character, on the copied side a missing one
+///
+/// => a dropped character only counts once instead of shifting everything after it
+pub fn align(sent: &str, copy: &str) -> Vec<(Option<char>, Option<char>)> {
+	let sent = sent.chars().collect::<Vec<_>>();
+	let copy = copy.chars().collect::<Vec<_>>();
+	let cost = |i: usize, j: usize| usize::from(sent[i - 1] != copy[j - 1]);
+
+	// distances[i][j]: edits between the first i sent and the first j copied characters
+	let mut distances = vec![vec![0; copy.len() + 1]; sent.len() + 1];
+
+	for i in 0..=sent.len() {
+		for j in 0..=copy.len() {
+			distances[i][j] = match (i, j) {
+				(0, j) => j,
+				(i, 0) => i,
+				(i, j) => {
+					let substitution = distances[i - 1][j - 1] + cost(i, j);
+					let deletion = distances[i - 1][j] + 1;
+					let insertion = distances[i][j - 1] + 1;
+
+					substitution.min(deletion).min(insertion)
+				}
+			};
+		}
+	}
+
+	// walk back from the end, preferring matches and substitutions
+	let mut pairs = vec![];
+	let (mut i, mut j) = (sent.len(), copy.len());
+
+	while i > 0 || j > 0 {
+		if i > 0 && j > 0 && distances[i][j] == distances[i - 1][j - 1] + cost(i, j) {
+			pairs.push((Some(sent[i - 1]), Some(copy[j - 1])));
+			i -= 1;
+			j -= 1;
+		} else if i > 0 && distances[i][j] == distances[i - 1][j] + 1 {
+			pairs.push((Some(sent[i - 1]), None));
+			i -= 1;
+		} else {
+			pairs.push((None, Some(copy[j - 1])));
+			j -= 1;
+		}
+	}
+
+	pairs.reverse();
+	pairs
+}
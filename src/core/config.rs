@@ -1,3 +1,4 @@
+use crate::prelude::KeyerMode;
 use serde::{Deserialize, Serialize};
 
 /// calculates the ms per unit for a given WPM
@@ -19,6 +20,8 @@ macro_rules! wpm {
 pub struct CwConfig {
 	pub input: InputConfig,
 	pub output: OutputConfig,
+	#[serde(default)]
+	pub clock: ClockConfig,
 }
 
 impl Default for CwConfig {
@@ -37,7 +40,12 @@ impl Default for CwConfig {
 					fw_ms: wpm!(10),
 					freq: 550,
 				},
+				idle_ms: 3000,
+				correction_ms: 1500,
+				debounce_ms: 20,
+				keyer: KeyerMode::IambicB,
 			},
+			clock: ClockConfig::default(),
 		}
 	}
 }
@@ -60,8 +68,26 @@ pub struct OutputConfig {
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct InputConfig {
 	pub signal: SignalConfig,
-	// impl this to replace const MAX_MS
-	// pub idle_ms: u32,
-	// impl this to send Error Correction
-	// pub correction_ms: u32,
+	/// ms of silence after which the input is passed on
+	pub idle_ms: u32,
+	/// ms a key has to be held down to send an Error Correction
+	pub correction_ms: u32,
+	/// ms a key state has to last to count as a mark or a space
+	///
+	/// => shorter state changes, like contact bounce, are merged into the surrounding signal
+	pub debounce_ms: u32,
+	/// squeeze behaviour of the paddle keyer
+	pub keyer: KeyerMode,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct ClockConfig {
+	/// offset of the local time to UTC in minutes
+	///
+	/// => the local time is unknown until it is configured, so UTC is used instead
+	pub utc_offset_min: Option<i32>,
+	/// use the 12-hour clock instead of the 24-hour clock
+	pub hour_12: bool,
+	/// show UTC instead of the local time by default
+	pub utc: bool,
 }
@@ -1,14 +1,57 @@
 use crate::prelude::CwConfig;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 
 pub trait CwContext {
 	/// get current configuration
 	fn config(&self) -> CwConfig;
 
-	/// get current time in milliseconds
-	fn time(&self) -> u32;
+	/// change and persist the configuration
+	///
+	/// => the configuration is left unchanged if it can't be persisted
+	fn set_config(&mut self, config: CwConfig) -> anyhow::Result<()>;
+
+	/// get current time in milliseconds since the UNIX epoch
+	fn time(&self) -> u64;
+
+	/// get milliseconds from a monotonic clock, for measuring durations
+	///
+	/// => unlike `time`, it never jumps, e.g. when the wall clock is synced
+	fn uptime_ms(&self) -> u64;
 
 	/// close app or go to sleep
 	fn quit(&self);
 
-	// todo: add database I/O
+	/// get a random number
+	fn random(&mut self) -> u64;
+
+	/// read a value from the database
+	fn read(&self, key: &str) -> Option<Value>;
+
+	/// write a value to the database, `None` removes it
+	///
+	/// => the database is left unchanged if the value can't be persisted
+	fn write(&mut self, key: &str, value: Option<Value>) -> anyhow::Result<()>;
 }
+
+/// typed helpers for every context, whether it is generic or a trait object
+pub trait CwContextExt: CwContext {
+	/// get a random index below `len`
+	fn random_index(&mut self, len: usize) -> usize {
+		(self.random() % len.max(1) as u64) as usize
+	}
+
+	/// read a typed value from the database
+	fn load<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+		self.read(key)
+			.and_then(|value| serde_json::from_value(value).ok())
+	}
+
+	/// write a typed value to the database
+	fn store<T: Serialize>(&mut self, key: &str, value: &T) -> anyhow::Result<()> {
+		let value = serde_json::to_value(value)?;
+		self.write(key, Some(value))
+	}
+}
+
+impl<C: CwContext + ?Sized> CwContextExt for C {}
@@ -2,4 +2,7 @@ use crate::prelude::CwContext;
 
 pub trait CwController<Input, Output>: Default {
 	fn tick(&mut self, ctx: &mut impl CwContext, input: Input) -> Output;
+
+	/// receives the input decoded so far, before it is passed to `tick`
+	fn preview(&mut self, _ctx: &mut impl CwContext, _input: &Input) {}
 }
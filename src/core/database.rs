@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// in-memory key/value database that apps persist their state in
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CwDatabase(BTreeMap<String, Value>);
+
+impl CwDatabase {
+	pub fn read(&self, key: &str) -> Option<Value> {
+		self.0.get(key).cloned()
+	}
+
+	/// `None` removes the value
+	pub fn write(&mut self, key: &str, value: Option<Value>) {
+		match value {
+			Some(value) => self.0.insert(key.to_string(), value),
+			None => self.0.remove(key),
+		};
+	}
+}
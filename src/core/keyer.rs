@@ -0,0 +1,235 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// squeeze behaviour of the keyer, i.e. what happens while both paddles are pressed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum KeyerMode {
+	/// alternates dits and dahs, stops right after the current element once a squeeze is released
+	///
+	/// => a tap of the opposite paddle on its own is still remembered
+	IambicA,
+	/// alternates dits and dahs, sends one more opposite element if a paddle was squeezed during the current one
+	#[default]
+	IambicB,
+	/// repeats the element of the paddle that was pressed last
+	Ultimatic,
+}
+
+/// state of the dit and dah contacts of a dual-lever paddle
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Paddles {
+	pub dit: bool,
+	pub dah: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum KeyerState {
+	#[default]
+	Idle,
+	/// sending a dah (true) or a dit (false)
+	Element(bool),
+	/// silence of one unit after each element
+	Gap,
+}
+
+/// turns paddle input into correctly timed key input for a `SignalController`
+#[derive(Default)]
+pub struct Keyer {
+	state: KeyerState,
+	last_element: bool,
+	last_pressed: bool,
+	last_paddles: Paddles,
+	memory: Option<bool>,
+	/// whether both paddles were pressed during the current element
+	squeezed: bool,
+	elapsed_ms: u32,
+	timer: DeltaTimer,
+}
+
+impl CwController<Paddles, bool> for Keyer {
+	fn tick(&mut self, ctx: &mut impl CwContext, paddles: Paddles) -> bool {
+		let delta_ms = self.timer.delta_ms(ctx.uptime_ms());
+		self.elapsed_ms = self.elapsed_ms.saturating_add(delta_ms);
+
+		let config = ctx.config().input;
+		let unit_ms = config.signal.unit_ms;
+
+		// remember the paddle that was pressed last for Ultimatic
+		if paddles.dit && !self.last_paddles.dit {
+			self.last_pressed = false;
+		}
+		if paddles.dah && !self.last_paddles.dah {
+			self.last_pressed = true;
+		}
+		self.last_paddles = paddles;
+
+		// remember the opposite paddle while an element is being sent
+		if self.state != KeyerState::Idle {
+			match self.last_element {
+				true if paddles.dit => self.memory = Some(false),
+				false if paddles.dah => self.memory = Some(true),
+				_ => {}
+			}
+
+			self.squeezed |= paddles.dit && paddles.dah;
+		}
+
+		if let KeyerState::Element(dah) = self.state {
+			let element_ms = if dah { unit_ms * 3 } else { unit_ms };
+
+			if self.elapsed_ms >= element_ms {
+				self.elapsed_ms -= element_ms;
+				self.state = KeyerState::Gap;
+			}
+		}
+
+		if self.state == KeyerState::Gap && self.elapsed_ms >= unit_ms {
+			self.elapsed_ms -= unit_ms;
+			self.state = KeyerState::Idle;
+		}
+
+		if self.state == KeyerState::Idle {
+			match self.next_element(config.keyer, paddles) {
+				Some(element) => {
+					self.state = KeyerState::Element(element);
+					self.last_element = element;
+					self.memory = None;
+					self.squeezed = false;
+				}
+				None => self.elapsed_ms = 0,
+			}
+		}
+
+		matches!(self.state, KeyerState::Element(_))
+	}
+}
+
+impl Keyer {
+	fn next_element(&self, mode: KeyerMode, paddles: Paddles) -> Option<bool> {
+		// only Iambic B sends one more element after a squeeze was released
+		let memory = match mode != KeyerMode::IambicB && self.squeezed {
+			true => None,
+			false => self.memory,
+		};
+
+		match (paddles.dit, paddles.dah) {
+			(true, true) => Some(match mode {
+				KeyerMode::IambicA | KeyerMode::IambicB => !self.last_element,
+				KeyerMode::Ultimatic => self.last_pressed,
+			}),
+			_ if memory.is_some() => memory,
+			(true, false) => Some(false),
+			(false, true) => Some(true),
+			(false, false) => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sim_context::SimContext;
+
+	const NONE: Paddles = Paddles {
+		dit: false,
+		dah: false,
+	};
+	const DIT: Paddles = Paddles {
+		dit: true,
+		dah: false,
+	};
+	const DAH: Paddles = Paddles {
+		dit: false,
+		dah: true,
+	};
+	const BOTH: Paddles = Paddles {
+		dit: true,
+		dah: true,
+	};
+
+	/// the durations of the marks and gaps, at the default 15 WPM a dit is 80 ms
+	fn key(mode: KeyerMode, script: &[(Paddles, u32)]) -> Vec<(bool, u32)> {
+		let mut ctx = SimContext::default();
+		ctx.config.input.keyer = mode;
+
+		let mut runs: Vec<(bool, u32)> = vec![];
+
+		for is_on in ctx.run(&mut Keyer::default(), script, 1) {
+			match runs.last_mut() {
+				Some((last, duration)) if *last == is_on => *duration += 1,
+				_ => runs.push((is_on, 1)),
+			}
+		}
+
+		// the silence at the end
+		if runs.last().is_some_and(|(is_on, _)| !is_on) {
+			runs.pop();
+		}
+		runs
+	}
+
+	#[test]
+	fn held_paddle() {
+		let dits = [(true, 80), (false, 80), (true, 80), (false, 80), (true, 80)];
+		assert_eq!(key(KeyerMode::IambicB, &[(DIT, 400), (NONE, 400)]), dits);
+
+		let dahs = [(true, 240), (false, 80), (true, 240)];
+		assert_eq!(key(KeyerMode::IambicB, &[(DAH, 560), (NONE, 400)]), dahs);
+	}
+
+	#[test]
+	fn squeeze_release() {
+		// a dit, squeezed during it and released before it ends
+		let script = [(DIT, 40), (BOTH, 20), (NONE, 400)];
+
+		assert_eq!(key(KeyerMode::IambicA, &script), [(true, 80)]);
+		assert_eq!(
+			key(KeyerMode::IambicB, &script),
+			[(true, 80), (false, 80), (true, 240)]
+		);
+	}
+
+	#[test]
+	fn held_squeeze() {
+		// released during the gap after the second dit
+		let script = [(DIT, 40), (BOTH, 600), (NONE, 400)];
+		let alternating = vec![
+			(true, 80),
+			(false, 80),
+			(true, 240),
+			(false, 80),
+			(true, 80),
+		];
+
+		assert_eq!(key(KeyerMode::IambicA, &script), alternating);
+		assert_eq!(
+			key(KeyerMode::IambicB, &script),
+			[alternating, vec![(false, 80), (true, 240)]].concat()
+		);
+	}
+
+	#[test]
+	fn opposite_tap() {
+		// a dah, then a short dit tap while it is still being sent
+		let script = [(DAH, 100), (NONE, 50), (DIT, 30), (NONE, 400)];
+		let dah_dit = [(true, 240), (false, 80), (true, 80)];
+
+		assert_eq!(key(KeyerMode::IambicA, &script), dah_dit);
+		assert_eq!(key(KeyerMode::IambicB, &script), dah_dit);
+	}
+
+	#[test]
+	fn ultimatic() {
+		// the dah paddle is pressed last, so dahs are repeated while both are held
+		let script = [(DIT, 40), (BOTH, 600), (NONE, 400)];
+		let dit_dahs = [
+			(true, 80),
+			(false, 80),
+			(true, 240),
+			(false, 80),
+			(true, 240),
+		];
+
+		assert_eq!(key(KeyerMode::Ultimatic, &script), dit_dahs);
+	}
+}
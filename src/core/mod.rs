@@ -2,16 +2,28 @@ pub mod apps;
 pub mod config;
 pub mod context;
 pub mod controller;
+pub mod database;
 pub mod element;
+pub mod keyer;
+pub mod random;
+pub mod scheduler;
 pub mod signal;
+pub mod speed;
 pub mod symbol;
+pub mod time;
 
 pub mod prelude {
 	pub use super::apps;
 	pub use super::config::*;
 	pub use super::context::*;
 	pub use super::controller::*;
+	pub use super::database::*;
 	pub use super::element::*;
+	pub use super::keyer::*;
+	pub use super::random::*;
+	pub use super::scheduler::*;
 	pub use super::signal::*;
+	pub use super::speed::*;
 	pub use super::symbol::*;
+	pub use super::time::*;
 }
@@ -0,0 +1,17 @@
+/// small pseudo random number generator (SplitMix64), so that any context can provide randomness
+#[derive(Debug, Default, Clone)]
+pub struct CwRandom(u64);
+
+impl CwRandom {
+	pub fn new(seed: u64) -> Self {
+		Self(seed)
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+		z ^ (z >> 31)
+	}
+}
@@ -0,0 +1,58 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const TIMERS_KEY: &str = "timers";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timer {
+	/// ms since the UNIX epoch at which the timer fires
+	pub due: u64,
+	/// sent once the timer fires
+	pub message: String,
+}
+
+/// timers that apps register to send output without any user input
+///
+/// => the pending timers are kept in the database, so they survive restarts
+pub struct Scheduler;
+
+impl Scheduler {
+	/// pending timers, ordered by due time
+	pub fn timers(ctx: &dyn CwContext) -> Vec<Timer> {
+		ctx.load(TIMERS_KEY).unwrap_or_default()
+	}
+
+	pub fn schedule(ctx: &mut dyn CwContext, timer: Timer) -> anyhow::Result<()> {
+		let mut timers = Self::timers(ctx);
+		timers.push(timer);
+		timers.sort_by_key(|timer| timer.due);
+		ctx.store(TIMERS_KEY, &timers)
+	}
+
+	/// removes the pending timer at the given index
+	pub fn cancel(ctx: &mut dyn CwContext, index: usize) -> Option<Timer> {
+		let mut timers = Self::timers(ctx);
+		let timer = (index < timers.len()).then(|| timers.remove(index))?;
+		ctx.store(TIMERS_KEY, &timers).ok()?;
+		Some(timer)
+	}
+
+	/// removes and returns all timers that are due
+	///
+	/// => they are kept pending as long as they can't be removed, so they aren't sent over and over
+	pub fn take_due(ctx: &mut dyn CwContext) -> Vec<Timer> {
+		let time = ctx.time();
+		let timers = Self::timers(ctx);
+
+		if !timers.iter().any(|timer| timer.due <= time) {
+			return vec![];
+		}
+
+		let (due, pending): (Vec<_>, Vec<_>) =
+			timers.into_iter().partition(|timer| timer.due <= time);
+		match ctx.store(TIMERS_KEY, &pending) {
+			Ok(()) => due,
+			Err(_) => vec![],
+		}
+	}
+}
@@ -13,56 +13,143 @@ struct Signal<T> {
 	duration: u32,
 }
 
+/// text that is sent with its own signal config, so several stations can be mixed together
+#[derive(Debug, Default, Clone)]
+pub struct Voice {
+	pub text: CwString,
+	/// uses the output config if `None`
+	pub signal: Option<SignalConfig>,
+	/// ms of silence before the voice starts
+	pub delay_ms: u32,
+}
+
+impl From<CwString> for Voice {
+	fn from(text: CwString) -> Self {
+		Self {
+			text,
+			..Default::default()
+		}
+	}
+}
+
+impl Voice {
+	/// ms from the start of the output until the voice has ended, including its delay
+	pub fn duration_ms(&self, output: SignalConfig) -> u32 {
+		self.signals(output)
+			.iter()
+			.map(|signal| signal.duration)
+			.sum()
+	}
+
+	fn signals(&self, output: SignalConfig) -> Vec<Signal<bool>> {
+		let config = SignalElementConfig::from(self.signal.unwrap_or(output));
+
+		let mut signals: Vec<Signal<bool>> = vec![];
+
+		if self.delay_ms > 0 {
+			signals.push(Signal {
+				value: false,
+				duration: self.delay_ms,
+			});
+		}
+
+		for symbol in &self.text.0 {
+			if let CwSymbol::Space = symbol {
+				// remove the last silent signal element
+				if let Some(last) = signals.last() {
+					if !last.value {
+						signals.pop();
+					}
+				}
+
+				// add a space
+				signals.push(Signal {
+					value: false,
+					duration: config.space_ms,
+				});
+			} else {
+				for signal in symbol.elements().0 {
+					// push either a dit or a dah
+					signals.push(Signal {
+						value: true,
+						duration: match signal {
+							true => config.dah_ms,
+							false => config.dit_ms,
+						},
+					});
+
+					// element break - the duration of silence after each signal element is one dit
+					signals.push(Signal {
+						value: false,
+						duration: config.dit_ms,
+					});
+				}
+
+				// remove the last element break to add a symbol break
+				signals.pop();
+
+				// add a break
+				signals.push(Signal {
+					value: false,
+					duration: config.break_ms,
+				});
+			}
+		}
+
+		signals
+	}
+}
+
+/// signals of a voice that is being sent
+struct VoiceSignals {
+	freq: u32,
+	signals: Vec<Signal<bool>>,
+	elapsed_ms: u32,
+}
+
+/// turns key input into symbols for the symbol controller, and its voices into tones
+///
+/// => the output is the frequency of every tone that is currently on
 #[derive(Default)]
 pub struct SignalController<C>
 where
-	C: CwController<CwString, CwString>,
+	C: CwController<CwString, Vec<Voice>>,
 {
 	symbol_controller: C,
 	mode: Mode,
-	buffer: Vec<Signal<bool>>,
+	voices: Vec<VoiceSignals>,
 	last_input_state: bool,
+	raw_input_state: bool,
+	raw_elapsed_ms: u32,
+	debounced_input_state: bool,
 	elapsed_ms: u32,
-	last_time: u32,
+	timer: DeltaTimer,
+	speed: SpeedTracker,
+	elements: CwElementString,
+	transcript: CwString,
 }
 
-impl<C> CwController<bool, Option<u32>> for SignalController<C>
+impl<C> CwController<bool, Vec<u32>> for SignalController<C>
 where
-	C: CwController<CwString, CwString>,
+	C: CwController<CwString, Vec<Voice>>,
 {
-	fn tick(&mut self, ctx: &mut impl CwContext, input: bool) -> Option<u32> {
-		let time = ctx.time();
-		if self.last_time == 0 {
-			self.last_time = time;
-		}
-		let delta_ms = time - self.last_time;
-		self.elapsed_ms += delta_ms;
-
-		let signal_on = match self.mode {
-			Mode::Input => self.input_tick(ctx, input),
-			Mode::Output => self.output_tick(ctx, input),
-		};
+	fn tick(&mut self, ctx: &mut impl CwContext, input: bool) -> Vec<u32> {
+		let delta_ms = self.timer.delta_ms(ctx.uptime_ms());
+		self.elapsed_ms = self.elapsed_ms.saturating_add(delta_ms);
 
-		let signal = match signal_on {
-			true => Some(match self.mode {
-				Mode::Input => ctx.config().input.signal.freq,
-				Mode::Output => ctx.config().output.signal.freq,
-			}),
-			false => None,
-		};
+		let input = self.debounce(input, delta_ms, ctx.config().input.debounce_ms);
 
-		self.last_time = time;
-
-		signal
+		match self.mode {
+			Mode::Input => self.input_tones(ctx, input),
+			Mode::Output => self.output_tick(ctx, input, delta_ms),
+		}
 	}
 }
 
 impl<C> SignalController<C>
 where
-	C: CwController<CwString, CwString>,
+	C: CwController<CwString, Vec<Voice>>,
 {
-	pub const MAX_MS: u32 = 3000; // todo: make this configurable
-
 	pub fn new(controller: C) -> Self {
 		Self {
 			symbol_controller: controller,
@@ -74,44 +161,99 @@ where
 		self.mode.clone()
 	}
 
+	/// estimated keying speed of the operator, once they have keyed anything
+	pub fn get_wpm(&self) -> Option<u32> {
+		self.speed.wpm()
+	}
+
+	pub fn get_symbol_controller(&self) -> &C {
+		&self.symbol_controller
+	}
+
+	/// symbols decoded from the current input so far
+	pub fn get_transcript(&self) -> &CwString {
+		&self.transcript
+	}
+
 	pub fn reset(&mut self) {
-		self.buffer.clear();
+		self.voices.clear();
+		self.elements.0.clear();
+		self.transcript.0.clear();
 		self.last_input_state = false;
 		self.elapsed_ms = 0;
 	}
 
+	/// only passes on a new input state once it has lasted for `debounce_ms`
+	fn debounce(&mut self, input_state: bool, delta_ms: u32, debounce_ms: u32) -> bool {
+		if input_state != self.raw_input_state {
+			self.raw_input_state = input_state;
+			self.raw_elapsed_ms = 0;
+		} else {
+			self.raw_elapsed_ms = self.raw_elapsed_ms.saturating_add(delta_ms);
+		}
+
+		if self.raw_elapsed_ms >= debounce_ms {
+			self.debounced_input_state = input_state;
+		}
+
+		self.debounced_input_state
+	}
+
+	fn input_tones(&mut self, ctx: &mut impl CwContext, input_state: bool) -> Vec<u32> {
+		match self.input_tick(ctx, input_state) {
+			true => vec![ctx.config().input.signal.freq],
+			false => vec![],
+		}
+	}
+
 	fn input_tick(&mut self, ctx: &mut impl CwContext, input_state: bool) -> bool {
+		let config = ctx.config().input;
 		let last_input_state = self.last_input_state;
 
 		match (last_input_state, input_state) {
-			(false, true) | (true, false) => {
-				self.buffer.push(Signal {
-					duration: self.elapsed_ms,
-					value: last_input_state,
-				});
+			(true, false) => {
+				self.push_mark(ctx, self.elapsed_ms);
+				self.elapsed_ms = 0;
+			}
+			(false, true) => {
+				// the gap is over, so its full duration decides whether it ended a character or word,
+				// then it refines the estimate
+				if !self.elements.0.is_empty() || !self.transcript.0.is_empty() {
+					self.push_breaks(ctx);
+					self.speed.track_gap(self.elapsed_ms, config.signal);
+				}
 
 				self.elapsed_ms = 0;
-				dbg!(&self.buffer);
 			}
 			(false, false) => {
-				// if the user is idle for long enough
-				// then pass the input buffer to the procedure and return control
-				if self.elapsed_ms >= Self::MAX_MS {
-					self.buffer.push(Signal {
-						duration: self.elapsed_ms,
-						value: false,
-					});
+				self.push_breaks(ctx);
 
-					let input_signals = self.buffer.clone();
-					let input = Self::signals_to_symbols(input_signals, &ctx.config());
+				// if the user is idle for long enough
+				// then pass the transcript to the procedure and return control
+				if self.elapsed_ms >= config.idle_ms {
+					let input = std::mem::take(&mut self.transcript);
 
 					let output = self.symbol_controller.tick(ctx, input);
-					let output_signals = Self::symbols_to_signals(output, &ctx.config());
-
-					self.reset();
-					self.mode = Mode::Output;
+					self.start_output(ctx, output);
+				}
 
-					self.buffer = output_signals;
+				// send due timers unprompted as long as the operator isn't keying
+				if self.mode == Mode::Input
+					&& self.elements.0.is_empty()
+					&& self.transcript.0.is_empty()
+				{
+					let timers = Scheduler::take_due(ctx);
+
+					if !timers.is_empty() {
+						let messages = timers
+							.iter()
+							.map(|timer| timer.message.as_str())
+							.collect::<Vec<_>>()
+							.join(" ");
+
+						let output = CwString::from(messages.as_str());
+						self.start_output(ctx, vec![Voice::from(output)]);
+					}
 				}
 			}
 			(true, true) => {}
@@ -121,108 +263,108 @@ where
 		input_state
 	}
 
-	fn output_tick(&mut self, ctx: &mut impl CwContext, input_state: bool) -> bool {
-		if input_state {
-			self.mode = Mode::Input;
-			self.reset();
-			return self.input_tick(ctx, input_state);
+	fn push_mark(&mut self, ctx: &mut impl CwContext, duration: u32) {
+		let config = ctx.config().input;
+
+		if duration >= config.correction_ms {
+			// a held down key discards the current character,
+			// or sends an Error Correction if there is none
+			match self.elements.0.is_empty() {
+				true => self.push_symbol(ctx, CwSymbol::Correction),
+				false => self.elements.0.clear(),
+			}
+			return;
 		}
 
-		if let Some(signal) = self.buffer.first() {
-			let output_state = signal.value;
+		// add a dah (true) or a dit (false)
+		// => the mark is classified with the estimate from before it, then refines the estimate
+		let is_dah = duration >= self.speed.thresholds(config.signal).dah_ms;
+		self.elements.0.push(is_dah);
 
-			if self.elapsed_ms >= signal.duration {
-				self.elapsed_ms = 0;
+		self.speed.track_mark(duration, config.signal.unit_ms);
+	}
 
-				if !self.buffer.is_empty() {
-					self.buffer.remove(0);
-				}
-			}
+	/// pushes a character once the current gap qualifies for a character break,
+	/// and a space once it qualifies for a word break
+	fn push_breaks(&mut self, ctx: &mut impl CwContext) {
+		let thresholds = self.speed.thresholds(ctx.config().input.signal);
 
-			output_state
-		} else {
-			self.reset();
-			self.mode = Mode::Input;
-			false
+		if self.elapsed_ms >= thresholds.break_ms && !self.elements.0.is_empty() {
+			let symbol = CwSymbol::from(&self.elements);
+			self.elements.0.clear();
+			self.push_symbol(ctx, symbol);
 		}
-	}
 
-	fn signals_to_symbols(signals: Vec<Signal<bool>>, config: &CwConfig) -> CwString {
-		let config = SignalElementConfig::from(config.input.signal);
+		if self.elapsed_ms > thresholds.space_ms
+			&& self
+				.transcript
+				.0
+				.last()
+				.is_some_and(|symbol| *symbol != CwSymbol::Space)
+		{
+			self.push_symbol(ctx, CwSymbol::Space);
+		}
+	}
 
-		let mut elements = CwElementString(vec![]);
-		let mut symbols: Vec<CwSymbol> = vec![];
+	fn push_symbol(&mut self, ctx: &mut impl CwContext, symbol: CwSymbol) {
+		self.transcript.0.push(symbol);
+		self.symbol_controller.preview(ctx, &self.transcript);
+	}
 
-		for signal in signals {
-			if signal.value {
-				// todo: send Error Correction symbol if ms >= max
-				// add a dah (true) or a dit (false)
-				elements.0.push(signal.duration >= config.dah_ms);
-			} else if signal.duration >= config.break_ms {
-				dbg!(elements.clone());
-				// convert elements to a symbol if silence qualifies for a character break
-				symbols.push(CwSymbol::from(&elements));
-				elements.0.clear();
+	fn start_output(&mut self, ctx: &mut impl CwContext, voices: Vec<Voice>) {
+		let output = ctx.config().output.signal;
 
-				// add a space
-				if signal.duration > config.space_ms {
-					symbols.push(CwSymbol::Space);
-				}
-			}
-		}
+		self.reset();
+		self.mode = Mode::Output;
 
-		CwString(symbols)
+		self.voices = voices
+			.iter()
+			.map(|voice| VoiceSignals {
+				freq: voice.signal.unwrap_or(output).freq,
+				signals: voice.signals(output),
+				elapsed_ms: 0,
+			})
+			.collect();
 	}
 
-	fn symbols_to_signals(symbols: CwString, config: &CwConfig) -> Vec<Signal<bool>> {
-		let config = SignalElementConfig::from(config.output.signal);
+	fn output_tick(
+		&mut self,
+		ctx: &mut impl CwContext,
+		input_state: bool,
+		delta_ms: u32,
+	) -> Vec<u32> {
+		if input_state {
+			self.mode = Mode::Input;
+			self.reset();
+			return self.input_tones(ctx, input_state);
+		}
 
-		let mut signals: Vec<Signal<bool>> = vec![];
+		if self.voices.is_empty() {
+			self.reset();
+			self.mode = Mode::Input;
+			return vec![];
+		}
 
-		for symbol in symbols.0 {
-			if let CwSymbol::Space = symbol {
-				// remove the last silent signal element
-				if let Some(last) = signals.last() {
-					if !last.value {
-						signals.pop();
-					}
-				}
+		let mut tones = vec![];
 
-				// add a space
-				signals.push(Signal {
-					value: false,
-					duration: config.space_ms,
-				});
-			} else {
-				for signal in symbol.elements().0 {
-					// push either a dit or a dah
-					signals.push(Signal {
-						value: true,
-						duration: match signal {
-							true => config.dah_ms,
-							false => config.dit_ms,
-						},
-					});
+		for voice in &mut self.voices {
+			voice.elapsed_ms = voice.elapsed_ms.saturating_add(delta_ms);
 
-					// element break - the duration of silence after each signal element is one dit
-					signals.push(Signal {
-						value: false,
-						duration: config.dit_ms,
-					});
+			if let Some(signal) = voice.signals.first() {
+				if signal.value {
+					tones.push(voice.freq);
 				}
 
-				// remove the last element break to add a symbol break
-				signals.pop();
-
-				// add a break
-				signals.push(Signal {
-					value: false,
-					duration: config.break_ms,
-				});
+				if voice.elapsed_ms >= signal.duration {
+					voice.elapsed_ms = 0;
+					voice.signals.remove(0);
+				}
 			}
 		}
 
-		signals
+		self.voices.retain(|voice| !voice.signals.is_empty());
+
+		tones
 	}
 }
 
@@ -246,3 +388,160 @@ impl From<SignalConfig> for SignalElementConfig {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sim_context::SimContext;
+
+	/// records the input that is passed on
+	#[derive(Default)]
+	struct Recorder(Vec<String>);
+
+	impl CwController<CwString, Vec<Voice>> for Recorder {
+		fn tick(&mut self, _ctx: &mut impl CwContext, input: CwString) -> Vec<Voice> {
+			let input = String::from(&input);
+
+			if !input.is_empty() {
+				self.0.push(input.trim_end().to_string());
+			}
+
+			vec![]
+		}
+	}
+
+	/// the default input config keys at 15 WPM, so a dit is 80 ms, with a 20 ms debounce
+	fn decode(script: &[(bool, u32)], step_ms: u32) -> Vec<String> {
+		let mut ctx = SimContext::default();
+		let mut controller = SignalController::new(Recorder::default());
+
+		ctx.run(&mut controller, script, step_ms);
+		controller.symbol_controller.0
+	}
+
+	#[test]
+	fn clean_input() {
+		let script = [(true, 80), (false, 360), (true, 240), (false, 4000)];
+		assert_eq!(decode(&script, 1), ["ET"]);
+	}
+
+	#[test]
+	fn bouncy_press() {
+		let script = [
+			(true, 3),
+			(false, 2),
+			(true, 4),
+			(false, 3),
+			(true, 80),
+			(false, 4000),
+		];
+		assert_eq!(decode(&script, 1), ["E"]);
+	}
+
+	#[test]
+	fn bouncy_release() {
+		let script = [
+			(true, 240),
+			(false, 4),
+			(true, 3),
+			(false, 2),
+			(true, 5),
+			(false, 4000),
+		];
+		assert_eq!(decode(&script, 1), ["T"]);
+	}
+
+	#[test]
+	fn glitch_in_gap() {
+		let script = [
+			(true, 80),
+			(false, 150),
+			(true, 5),
+			(false, 200),
+			(true, 80),
+			(false, 4000),
+		];
+		assert_eq!(decode(&script, 1), ["EE"]);
+	}
+
+	#[test]
+	fn bouncy_sequence() {
+		// `PARIS` with contact bounce at the start and end of every mark
+		let mut script = vec![];
+
+		for (index, symbol) in CwString::from("PARIS").0.iter().enumerate() {
+			if index > 0 {
+				script.push((false, 360));
+			}
+
+			for (index, is_dah) in symbol.elements().0.iter().enumerate() {
+				if index > 0 {
+					script.push((false, 80));
+				}
+
+				let mark_ms = if *is_dah { 240 } else { 80 };
+				script.extend([
+					(true, 2),
+					(false, 3),
+					(true, mark_ms),
+					(false, 2),
+					(true, 3),
+				]);
+			}
+		}
+		script.push((false, 4000));
+
+		assert_eq!(decode(&script, 1), ["PARIS"]);
+	}
+
+	#[test]
+	fn held_key_in_character() {
+		// `HEL`, a dit of the next character, then the key is held down
+		let signal = CwConfig::default().input.signal;
+		let mut script = SimContext::keying("HEL", signal);
+		script.extend([
+			(false, 360),
+			(true, 80),
+			(false, 80),
+			(true, 2000),
+			(false, 4000),
+		]);
+
+		assert_eq!(decode(&script, 1), ["HEL"]);
+	}
+
+	#[test]
+	fn held_key_after_character() {
+		let signal = CwConfig::default().input.signal;
+		let mut script = SimContext::keying("HEL", signal);
+		script.extend([(false, 360), (true, 2000), (false, 4000)]);
+
+		// the Error Correction erases the previous character
+		let decoded = decode(&script, 1);
+		assert_eq!(decoded, ["HEL*"]);
+
+		let input = CwString::from(decoded[0].as_str()).normalized();
+		assert_eq!(String::from(&input), "HE");
+	}
+
+	#[test]
+	fn clock_jump() {
+		let mut ctx = SimContext::default();
+		let mut controller = SignalController::new(Recorder::default());
+
+		// e.g. the first sync of a device without a real-time clock, from 1970 to 2025
+		controller.tick(&mut ctx, false);
+		ctx.advance(55 * 365 * 86_400_000);
+		controller.tick(&mut ctx, false);
+
+		ctx.run(&mut controller, &[(true, 80), (false, 4000)], 1);
+		assert_eq!(controller.symbol_controller.0, ["E"]);
+	}
+
+	#[test]
+	fn long_idle() {
+		// an hour per tick for more than 50 days
+		let script = vec![(false, 3_600_000); 1300];
+		assert!(decode(&script, 3_600_000).is_empty());
+	}
+}
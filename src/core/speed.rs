@@ -0,0 +1,255 @@
+use crate::prelude::SignalConfig;
+
+/// durations that tell the kinds of keyed marks and gaps apart
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+	/// marks at least this long are dahs
+	pub dah_ms: u32,
+	/// gaps at least this long end a character
+	pub break_ms: u32,
+	/// gaps longer than this end a word
+	pub space_ms: u32,
+}
+
+/// estimates the operator's actual timing from the durations of their recently keyed marks and gaps
+///
+/// ## Marks
+/// if the longest recent mark is at least twice as long as the shortest one,
+/// the marks are split into dits and dahs at the midpoint between the two
+///
+/// otherwise all recent marks are of the same kind,
+/// which is decided by comparing their average against the last estimate
+///
+/// ## Gaps
+/// gaps within a character are told apart by the unit from the marks,
+/// the longer gaps are split into character and word gaps the same way as the marks,
+/// so both standard and Farnsworth spacing are followed
+///
+/// => the recent durations are classified again with every update,
+/// so a misclassified one is corrected once the operator keeps a new speed
+///
+/// => as long as nothing was observed, the configured timing is used
+#[derive(Debug, Default, Clone)]
+pub struct SpeedTracker {
+	marks: Vec<u32>,
+	gaps: Vec<u32>,
+	unit_ms: Option<u32>,
+	char_gap_ms: Option<u32>,
+}
+
+impl SpeedTracker {
+	/// number of recent marks or gaps to estimate the timing from
+	const WINDOW: usize = 12;
+
+	pub fn unit_ms(&self) -> Option<u32> {
+		self.unit_ms
+	}
+
+	pub fn wpm(&self) -> Option<u32> {
+		self.unit_ms.map(|unit_ms| crate::wpm!(unit_ms.max(1)))
+	}
+
+	/// the current thresholds, based on the configured timing until the operator has keyed enough
+	///
+	/// ## Thresholds
+	/// - dah: two units, between a dit (1) and a dah (3)
+	/// - break: two units, between an element gap (1) and a character gap (3 or more)
+	/// - space: halfway between a character gap (3) and a word gap (7), in Farnsworth units
+	pub fn thresholds(&self, config: SignalConfig) -> Thresholds {
+		let unit_ms = self.unit_ms.unwrap_or(config.unit_ms);
+		let char_gap_ms = self.char_gap_ms(config);
+
+		Thresholds {
+			dah_ms: unit_ms * 2,
+			break_ms: unit_ms * 2,
+			space_ms: char_gap_ms * 5 / 3,
+		}
+	}
+
+	/// adds a keyed mark and updates the estimate
+	///
+	/// `initial_unit_ms` is used to classify the marks as long as there is no estimate yet
+	pub fn track_mark(&mut self, mark_ms: u32, initial_unit_ms: u32) {
+		Self::push(&mut self.marks, mark_ms);
+
+		let unit_ms = self.unit_ms.unwrap_or(initial_unit_ms);
+
+		// all marks are either dits or dahs
+		let dah_threshold = Self::split(&self.marks).unwrap_or_else(|| {
+			match Self::mean(&self.marks).unwrap_or_default() >= unit_ms * 2 {
+				true => 0,
+				false => u32::MAX,
+			}
+		});
+
+		let units = self
+			.marks
+			.iter()
+			.map(|&mark| match mark >= dah_threshold {
+				true => mark / 3,
+				false => mark,
+			})
+			.sum::<u32>();
+
+		self.unit_ms = Some((units / self.marks.len() as u32).max(1));
+	}
+
+	/// adds a gap between two marks and updates the estimate
+	///
+	/// => gaps within a character carry no information the marks don't have, so they are skipped
+	pub fn track_gap(&mut self, gap_ms: u32, config: SignalConfig) {
+		if gap_ms < self.thresholds(config).break_ms {
+			return;
+		}
+
+		let char_gap_ms = self.char_gap_ms(config);
+		Self::push(&mut self.gaps, gap_ms);
+
+		self.char_gap_ms = match Self::split(&self.gaps) {
+			Some(space_ms) => {
+				let char_gaps = self
+					.gaps
+					.iter()
+					.copied()
+					.filter(|&gap| gap < space_ms)
+					.collect::<Vec<_>>();
+
+				Self::mean(&char_gaps)
+			}
+			None => {
+				let mean = Self::mean(&self.gaps).unwrap_or(char_gap_ms);
+
+				// all gaps are either character or word gaps
+				match mean > char_gap_ms * 5 / 3 {
+					true => Some(mean * 3 / 7),
+					false => Some(mean),
+				}
+			}
+		};
+	}
+
+	/// character gaps are at least three units long, even if the operator runs them together
+	fn char_gap_ms(&self, config: SignalConfig) -> u32 {
+		let unit_ms = self.unit_ms.unwrap_or(config.unit_ms);
+
+		self.char_gap_ms
+			.unwrap_or(config.fw_ms.max(config.unit_ms) * 3)
+			.max(unit_ms * 3)
+	}
+
+	/// the midpoint between the shortest and longest duration, if they are of two kinds
+	fn split(window: &[u32]) -> Option<u32> {
+		let min = *window.iter().min()?;
+		let max = *window.iter().max()?;
+
+		(max >= min * 2).then_some((min + max) / 2)
+	}
+
+	fn push(window: &mut Vec<u32>, duration_ms: u32) {
+		if window.len() >= Self::WINDOW {
+			window.remove(0);
+		}
+		window.push(duration_ms);
+	}
+
+	fn mean(window: &[u32]) -> Option<u32> {
+		match window.is_empty() {
+			true => None,
+			false => Some((window.iter().sum::<u32>() / window.len() as u32).max(1)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::prelude::*;
+	use crate::sim_context::SimContext;
+
+	const TEXT: &str = "PARIS PARIS THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG";
+
+	/// collects everything that is decoded
+	#[derive(Default)]
+	struct Recorder(String);
+
+	impl CwController<CwString, Vec<Voice>> for Recorder {
+		fn tick(&mut self, _ctx: &mut impl CwContext, input: CwString) -> Vec<Voice> {
+			self.0 += String::from(&input).trim_end();
+			vec![]
+		}
+	}
+
+	fn signal(wpm: u32, fw_wpm: u32) -> SignalConfig {
+		SignalConfig {
+			unit_ms: crate::wpm!(wpm),
+			fw_ms: crate::wpm!(fw_wpm),
+			..Default::default()
+		}
+	}
+
+	/// keys the parts one after another, with the input configured as given
+	fn decode(config: SignalConfig, parts: &[(&str, SignalConfig)]) -> String {
+		let mut ctx = SimContext::default();
+		ctx.config.input.signal = config;
+
+		let mut script = vec![];
+		for (index, (text, signal)) in parts.iter().enumerate() {
+			if index > 0 {
+				script.push((false, signal.fw_ms * 7));
+			}
+			script.extend(SimContext::keying(text, *signal));
+		}
+		script.push((false, ctx.config.input.idle_ms + 100));
+
+		let mut controller = SignalController::new(Recorder::default());
+		ctx.run(&mut controller, &script, 1);
+
+		controller.get_symbol_controller().0.clone()
+	}
+
+	#[test]
+	fn fixed_speed() {
+		for wpm in [5, 12, 15, 25, 40] {
+			let signal = signal(wpm, wpm);
+			assert_eq!(decode(signal, &[(TEXT, signal)]), TEXT, "{wpm} WPM");
+		}
+	}
+
+	#[test]
+	fn speeding_up() {
+		for (from, to) in [(15, 25), (15, 30), (15, 40), (12, 40)] {
+			let (from, to) = (signal(from, from), signal(to, to));
+			let decoded = decode(from, &[(TEXT, from), (TEXT, to)]);
+
+			assert!(decoded.ends_with("OVER THE LAZY DOG"), "{decoded}");
+		}
+	}
+
+	#[test]
+	fn slowing_down() {
+		for (from, to) in [(25, 15), (30, 15), (40, 12), (40, 15)] {
+			let (from, to) = (signal(from, from), signal(to, to));
+			let decoded = decode(from, &[(TEXT, from), (TEXT, to)]);
+
+			assert!(decoded.ends_with("OVER THE LAZY DOG"), "{decoded}");
+		}
+	}
+
+	#[test]
+	fn farnsworth() {
+		for (wpm, fw_wpm) in [(18, 5), (20, 10), (15, 8)] {
+			let signal = signal(wpm, fw_wpm);
+			assert_eq!(
+				decode(signal, &[(TEXT, signal)]),
+				TEXT,
+				"{wpm}/{fw_wpm} WPM"
+			);
+		}
+	}
+
+	#[test]
+	fn unconfigured_farnsworth() {
+		// the longer character gaps are picked up once a word gap was keyed
+		let decoded = decode(signal(18, 18), &[(TEXT, signal(18, 5))]);
+		assert!(decoded.ends_with("OVER THE LAZY DOG"), "{decoded}");
+	}
+}
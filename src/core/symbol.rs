@@ -45,11 +45,48 @@ impl From<&CwElementString> for CwSymbol {
 pub struct CwString(pub Vec<CwSymbol>);
 
 impl CwString {
+	/// applies all Error Corrections and trims surrounding whitespace
+	///
+	/// ## Corrections
+	/// - `[HH]` right after a character erases that character
+	/// - `[HH]` after a space erases the whole previous word
+	///
+	/// => repeated corrections keep erasing backwards
 	pub fn normalized(&self) -> Self {
-		let str = String::from(self);
-		let str = str.trim();
-		// todo: handle [HH]
-		Self::from(str)
+		let mut symbols: Vec<CwSymbol> = vec![];
+
+		for symbol in &self.0 {
+			match symbol {
+				CwSymbol::Correction => match symbols.last() {
+					Some(CwSymbol::Space) => {
+						while symbols.last() == Some(&CwSymbol::Space) {
+							symbols.pop();
+						}
+						while symbols.last().is_some_and(|s| *s != CwSymbol::Space) {
+							symbols.pop();
+						}
+					}
+					_ => {
+						symbols.pop();
+					}
+				},
+				symbol => symbols.push(symbol.clone()),
+			}
+		}
+
+		let str = String::from(&Self(symbols));
+		Self::from(str.trim())
+	}
+
+	/// splits off the first word, e.g. `TM 5M` => (`TM`, `5M`)
+	pub fn split_first_word(&self) -> (Self, Self) {
+		match self.0.iter().position(|symbol| *symbol == CwSymbol::Space) {
+			Some(index) => (
+				Self(self.0[..index].to_vec()),
+				Self(self.0[index + 1..].to_vec()),
+			),
+			None => (self.clone(), Self::default()),
+		}
 	}
 }
 
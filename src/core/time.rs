@@ -0,0 +1,86 @@
+/// measures the ms that passed between consecutive ticks
+#[derive(Debug, Default, Clone)]
+pub struct DeltaTimer {
+	last_time: Option<u64>,
+}
+
+impl DeltaTimer {
+	/// longest delta, so a stalled tick or a jumping clock counts like a short pause
+	const MAX_DELTA_MS: u64 = 1000;
+
+	/// ms since the last call
+	///
+	/// => is `0` on the first call and whenever the time went backwards
+	pub fn delta_ms(&mut self, time: u64) -> u32 {
+		let delta_ms = self
+			.last_time
+			.map_or(0, |last_time| time.saturating_sub(last_time));
+
+		self.last_time = Some(time);
+		delta_ms.min(Self::MAX_DELTA_MS) as u32
+	}
+}
+
+/// calendar date and time of day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+	pub year: i64,
+	pub month: u32,
+	pub day: u32,
+	pub hour: u32,
+	pub minute: u32,
+	pub second: u32,
+}
+
+impl DateTime {
+	/// converts ms since the UNIX epoch to a date and time shifted by a UTC offset in minutes
+	///
+	/// => uses the proleptic Gregorian calendar, see http://howardhinnant.github.io/date_algorithms.html
+	pub fn from_epoch_ms(time: u64, utc_offset_min: i32) -> Self {
+		let secs = (time / 1000) as i64 + utc_offset_min as i64 * 60;
+		let days = secs.div_euclid(86400);
+		let secs = secs.rem_euclid(86400) as u32;
+
+		let days = days + 719468;
+		let era = days.div_euclid(146097);
+		let day_of_era = days.rem_euclid(146097);
+		let year_of_era =
+			(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let month_index = (5 * day_of_year + 2) / 153;
+		let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+		let month = match month_index {
+			0..=9 => month_index + 3,
+			_ => month_index - 9,
+		} as u32;
+		let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+		Self {
+			year,
+			month,
+			day,
+			hour: secs / 3600,
+			minute: secs / 60 % 60,
+			second: secs % 60,
+		}
+	}
+
+	/// converts a UTC date and time back to ms since the UNIX epoch, the inverse of `from_epoch_ms`
+	///
+	/// => dates before the epoch are clamped to `0`
+	pub fn to_epoch_ms(&self) -> u64 {
+		let year = self.year - if self.month <= 2 { 1 } else { 0 };
+		let era = year.div_euclid(400);
+		let year_of_era = year.rem_euclid(400);
+		let month_index = match self.month {
+			3..=12 => self.month - 3,
+			_ => self.month + 9,
+		} as i64;
+		let day_of_year = (153 * month_index + 2) / 5 + self.day as i64 - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		let days = era * 146097 + day_of_era - 719468;
+
+		let secs = days * 86400 + (self.hour * 3600 + self.minute * 60 + self.second) as i64;
+		secs.max(0) as u64 * 1000
+	}
+}
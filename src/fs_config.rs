@@ -0,0 +1,106 @@
+use crate::prelude::CwConfig;
+use anyhow::{bail, Context, Result};
+use std::{
+	env::args,
+	fs::{create_dir_all, read_to_string, write},
+	path::{Path, PathBuf},
+};
+
+const CONFIG_DIR: &str = "cwos";
+const CONFIG_FILE: &str = "config.json";
+
+/// uses the user's config directory if no path is given
+pub fn config_path(path: Option<PathBuf>) -> Result<PathBuf> {
+	if let Some(path) = path {
+		return Ok(path);
+	}
+
+	let mut path = dirs::config_dir().context("could not get config directory")?;
+	path.push(CONFIG_DIR);
+	path.push(CONFIG_FILE);
+	Ok(path)
+}
+
+/// loads the config file, or creates it with the defaults if it doesn't exist yet
+pub fn load_config(path: &Path) -> Result<CwConfig> {
+	if !path.exists() {
+		let config = CwConfig::default();
+		save_config(path, &config)?;
+		return Ok(config);
+	}
+
+	let string = read_to_string(path)
+		.with_context(|| format!("failed to read config file {}", path.display()))?;
+
+	let config = serde_json::from_str(&string)
+		.with_context(|| format!("invalid config file {}", path.display()))?;
+
+	validate(&config).with_context(|| format!("invalid config file {}", path.display()))?;
+	Ok(config)
+}
+
+/// rejects values that parse, but would break the timing, e.g. a unit of 0 ms
+fn validate(config: &CwConfig) -> Result<()> {
+	let signals = [
+		("input", config.input.signal),
+		("output", config.output.signal),
+	];
+
+	for (name, signal) in signals {
+		if signal.unit_ms == 0 {
+			bail!("{name}.signal.unit_ms must be greater than 0");
+		}
+
+		if signal.fw_ms < signal.unit_ms {
+			bail!("{name}.signal.fw_ms must be at least {name}.signal.unit_ms");
+		}
+	}
+
+	if config.input.idle_ms == 0 {
+		bail!("input.idle_ms must be greater than 0");
+	}
+
+	if config.input.correction_ms == 0 {
+		bail!("input.correction_ms must be greater than 0");
+	}
+
+	Ok(())
+}
+
+pub fn save_config(path: &Path, config: &CwConfig) -> Result<()> {
+	if let Some(dir) = path.parent() {
+		create_dir_all(dir)
+			.with_context(|| format!("failed to create config directory {}", dir.display()))?;
+	}
+
+	let string = serde_json::to_string_pretty(config)?;
+	write(path, string).with_context(|| format!("failed to write config file {}", path.display()))
+}
+
+/// command line arguments
+#[derive(Debug, Default)]
+pub struct Args {
+	/// `--config <path>`
+	pub config: Option<PathBuf>,
+	/// the remaining arguments, e.g. a subcommand and its arguments
+	pub command: Vec<String>,
+}
+
+/// parses the `--config <path>` argument and passes any other arguments on as the command
+pub fn parse_args() -> Result<Args> {
+	let mut args = args().skip(1);
+	let mut parsed = Args::default();
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--config" => {
+				let arg = args.next().context("missing path after --config")?;
+				parsed.config = Some(PathBuf::from(arg));
+			}
+			_ if arg.starts_with("--") => bail!("unknown argument: {arg}"),
+			_ => parsed.command.push(arg),
+		}
+	}
+
+	Ok(parsed)
+}
@@ -1,8 +1,10 @@
 use std::{
 	fs::{read_to_string, write},
+	ops::{Deref, DerefMut},
 	path::PathBuf,
 };
 
+use anyhow::{Context, Result};
 use serde::{de::DeserializeOwned, Serialize};
 
 const DB_FILE: &str = "cwos_db.json";
@@ -15,25 +17,49 @@ impl<T> FsDatabase<T>
 where
 	T: Default + Serialize + DeserializeOwned,
 {
-	fn path() -> PathBuf {
-		let mut path = dirs::home_dir().expect("could not get HOME directory");
+	fn path() -> Result<PathBuf> {
+		let mut path = dirs::home_dir().context("could not get HOME directory")?;
 		path.push(DB_FILE);
-		path
+		Ok(path)
 	}
 
-	pub fn load() -> T {
-		let path = Self::path();
+	pub fn load() -> Result<Self> {
+		let path = Self::path()?;
 		if !path.exists() {
-			T::default()
-		} else {
-			let string = read_to_string(path).expect("failed to read db file");
-			serde_json::from_str(&string).expect("invalid db file")
+			return Ok(Self(T::default()));
 		}
+
+		let string = read_to_string(&path)
+			.with_context(|| format!("failed to read db file {}", path.display()))?;
+		let db = serde_json::from_str(&string)
+			.with_context(|| format!("invalid db file {}", path.display()))?;
+
+		Ok(Self(db))
+	}
+
+	pub fn save(&self) -> Result<()> {
+		let path = Self::path()?;
+		let db = serde_json::to_string_pretty(&self.0).context("failed to serialize db file")?;
+		write(&path, db).with_context(|| format!("failed to write db file {}", path.display()))
 	}
+}
 
-	pub fn save(&self) {
-		let path = Self::path();
-		let db = serde_json::to_string_pretty(&self.0).expect("failed to serialize db file");
-		write(path, db).expect("failed to write to db file");
+impl<T> Deref for FsDatabase<T>
+where
+	T: Default + Serialize + DeserializeOwned,
+{
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T> DerefMut for FsDatabase<T>
+where
+	T: Default + Serialize + DeserializeOwned,
+{
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
 	}
 }
@@ -1,6 +1,9 @@
 pub mod core;
 pub mod ui;
 
+pub mod adif;
+pub mod fs_config;
 pub mod fs_database;
+pub mod sim_context;
 pub mod std_context;
 pub use core::prelude;
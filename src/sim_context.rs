@@ -0,0 +1,166 @@
+use crate::prelude::*;
+use serde_json::Value;
+use std::cell::Cell;
+
+/// deterministic context with a manually advanced clock, for testing and replay
+#[derive(Default)]
+pub struct SimContext {
+	pub config: CwConfig,
+	pub database: CwDatabase,
+	/// seeded with `0` by default
+	pub random: CwRandom,
+	time: u64,
+	quit_requests: Cell<u32>,
+}
+
+impl CwContext for SimContext {
+	fn config(&self) -> CwConfig {
+		self.config.clone()
+	}
+
+	fn set_config(&mut self, config: CwConfig) -> anyhow::Result<()> {
+		self.config = config;
+		Ok(())
+	}
+
+	fn time(&self) -> u64 {
+		self.time
+	}
+
+	/// the simulated clock only moves forward, so it is monotonic as well
+	fn uptime_ms(&self) -> u64 {
+		self.time
+	}
+
+	fn quit(&self) {
+		self.quit_requests.set(self.quit_requests.get() + 1);
+	}
+
+	fn random(&mut self) -> u64 {
+		self.random.next_u64()
+	}
+
+	fn read(&self, key: &str) -> Option<Value> {
+		self.database.read(key)
+	}
+
+	fn write(&mut self, key: &str, value: Option<Value>) -> anyhow::Result<()> {
+		self.database.write(key, value);
+		Ok(())
+	}
+}
+
+impl SimContext {
+	pub fn new(config: CwConfig) -> Self {
+		Self {
+			config,
+			..Default::default()
+		}
+	}
+
+	pub fn advance(&mut self, ms: u64) {
+		self.time += ms;
+	}
+
+	/// how often `quit` was called so far
+	pub fn quit_requests(&self) -> u32 {
+		self.quit_requests.get()
+	}
+
+	/// a script that keys a text with the given timing, as input for `run`
+	pub fn keying(text: &str, signal: SignalConfig) -> Vec<(bool, u32)> {
+		let SignalConfig { unit_ms, fw_ms, .. } = signal;
+		let mut script = vec![];
+
+		for (index, symbol) in CwString::from(text).0.iter().enumerate() {
+			if *symbol == CwSymbol::Space {
+				script.push((false, fw_ms * 7));
+				continue;
+			}
+
+			if index > 0 && script.last().is_some_and(|(is_on, _)| *is_on) {
+				script.push((false, fw_ms * 3));
+			}
+
+			for (index, is_dah) in symbol.elements().0.iter().enumerate() {
+				if index > 0 {
+					script.push((false, unit_ms));
+				}
+
+				script.push((true, if *is_dah { unit_ms * 3 } else { unit_ms }));
+			}
+		}
+
+		script
+	}
+
+	/// ticks the controller every `step_ms`, holding each scripted input for its duration in ms,
+	/// and collects the output of every tick
+	pub fn run<C, I, O>(&mut self, controller: &mut C, script: &[(I, u32)], step_ms: u32) -> Vec<O>
+	where
+		C: CwController<I, O>,
+		I: Clone,
+	{
+		let step_ms = step_ms.max(1);
+		let mut outputs = vec![];
+
+		for (input, duration) in script {
+			for _ in 0..duration.div_ceil(step_ms) {
+				outputs.push(controller.tick(self, input.clone()));
+				self.advance(step_ms as u64);
+			}
+		}
+
+		outputs
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::core::apps::AppLauncher;
+
+	/// durations of the consecutive ticks with the same tones, e.g. `([650], 60)`
+	fn runs(tones: &[Vec<u32>], step_ms: u32) -> Vec<(Vec<u32>, u32)> {
+		let mut runs: Vec<(Vec<u32>, u32)> = vec![];
+
+		for tone in tones {
+			match runs.last_mut() {
+				Some((last, duration)) if last == tone => *duration += step_ms,
+				_ => runs.push((tone.clone(), step_ms)),
+			}
+		}
+
+		runs
+	}
+
+	#[test]
+	fn keying_to_output() {
+		let mut ctx = SimContext::default();
+		let mut controller = SignalController::new(AppLauncher::default());
+
+		// launch the echo app at the configured 15 WPM, then wait for the reply
+		let mut script = SimContext::keying("EC", CwConfig::default().input.signal);
+		script.push((false, 6000));
+
+		let tones = ctx.run(&mut controller, &script, 5);
+		let marks = runs(&tones, 5)
+			.into_iter()
+			.filter(|(tone, _)| !tone.is_empty())
+			.collect::<Vec<_>>();
+
+		// the keyed marks at the input tone
+		let sidetone = [(80, 550), (240, 550), (80, 550), (240, 550), (80, 550)];
+		// the reply `EC` at the output tone and 20 WPM
+		let reply = [(60, 650), (180, 650), (60, 650), (180, 650), (60, 650)];
+
+		let expected = sidetone
+			.iter()
+			.chain(reply.iter())
+			.map(|&(duration, freq)| (vec![freq], duration))
+			.collect::<Vec<_>>();
+
+		assert_eq!(marks, expected);
+		assert_eq!(controller.get_mode(), Mode::Input);
+	}
+}
@@ -1,22 +1,84 @@
-use crate::prelude::*;
-use std::{process::exit, time::SystemTime};
+use crate::{
+	fs_config::{load_config, save_config},
+	fs_database::FsDatabase,
+	prelude::*,
+};
+use serde_json::Value;
+use std::{
+	path::PathBuf,
+	process::exit,
+	time::{Instant, SystemTime},
+};
 
-#[derive(Default)]
-pub struct StdContext;
+pub struct StdContext {
+	config: CwConfig,
+	config_path: PathBuf,
+	db: FsDatabase<CwDatabase>,
+	random: CwRandom,
+	start: Instant,
+}
+
+impl StdContext {
+	pub fn new(config_path: PathBuf) -> anyhow::Result<Self> {
+		Ok(Self {
+			config: load_config(&config_path)?,
+			config_path,
+			db: FsDatabase::load()?,
+			random: CwRandom::new(
+				SystemTime::now()
+					.duration_since(SystemTime::UNIX_EPOCH)
+					.map(|d| d.as_nanos() as u64)
+					.unwrap_or_default(),
+			),
+			start: Instant::now(),
+		})
+	}
+}
 
 impl CwContext for StdContext {
 	fn config(&self) -> CwConfig {
-		CwConfig::default()
+		self.config.clone()
 	}
 
-	fn time(&self) -> u32 {
+	fn set_config(&mut self, config: CwConfig) -> anyhow::Result<()> {
+		save_config(&self.config_path, &config)?;
+		self.config = config;
+		Ok(())
+	}
+
+	fn time(&self) -> u64 {
 		SystemTime::now()
 			.duration_since(SystemTime::UNIX_EPOCH)
-			.map(|d| d.as_millis() as u32)
-			.unwrap()
+			.map(|d| d.as_millis() as u64)
+			.unwrap_or_default()
+	}
+
+	fn uptime_ms(&self) -> u64 {
+		self.start.elapsed().as_millis() as u64
 	}
 
 	fn quit(&self) {
 		exit(0);
 	}
+
+	fn random(&mut self) -> u64 {
+		self.random.next_u64()
+	}
+
+	fn read(&self, key: &str) -> Option<Value> {
+		self.db.read(key)
+	}
+
+	fn write(&mut self, key: &str, value: Option<Value>) -> anyhow::Result<()> {
+		let previous = self.db.read(key);
+		self.db.write(key, value);
+
+		// keep memory and file in sync
+		if let Err(error) = self.db.save() {
+			self.db.write(key, previous);
+			return Err(error);
+		}
+
+		Ok(())
+	}
 }
@@ -18,15 +18,20 @@ pub fn load_icon() -> Option<Arc<IconData>> {
 	icon.map(Arc::new)
 }
 
-pub fn create_app() -> App {
-	App::default()
+pub fn create_app(cw_ctx: StdContext) -> App {
+	App {
+		audio: AudioState::default(),
+		cw_ctx,
+		keyer: Keyer::default(),
+		controller: SignalController::default(),
+	}
 }
 
 struct AudioState {
 	_stream: OutputStream, // must be kept alive
 	stream_handle: OutputStreamHandle,
-	sink: Option<Sink>,
-	last_signal: Option<u32>,
+	sinks: Vec<Sink>,
+	last_tones: Vec<u32>,
 }
 
 impl Default for AudioState {
@@ -36,8 +41,8 @@ impl Default for AudioState {
 		Self {
 			_stream: stream,
 			stream_handle,
-			sink: None,
-			last_signal: None,
+			sinks: vec![],
+			last_tones: vec![],
 		}
 	}
 }
@@ -46,9 +51,10 @@ const OFF_COLOR: Color32 = Color32::from_gray(64);
 const INPUT_COLOR: Color32 = Color32::from_gray(192);
 const OUTPUT_COLOR: Color32 = Color32::from_gray(128);
 
-#[derive(Default)]
 pub struct App {
 	audio: AudioState,
+	cw_ctx: StdContext,
+	keyer: Keyer,
 	controller: SignalController<AppLauncher>,
 }
 
@@ -59,27 +65,37 @@ impl eframe::App for App {
 
 			let mouse_input = egui_ctx.input(|i| i.pointer.primary_down());
 			let kb_input = egui_ctx.input(|i| i.key_down(Key::Space));
-			let input = mouse_input || kb_input;
+			let paddles = egui_ctx.input(|i| Paddles {
+				dit: i.key_down(Key::OpenBracket),
+				dah: i.key_down(Key::CloseBracket),
+			});
 
-			let mut cw_ctx = StdContext;
+			let keyer_input = self.keyer.tick(&mut self.cw_ctx, paddles);
+			let input = mouse_input || kb_input || keyer_input;
 
-			let signal = self.controller.tick(&mut cw_ctx, input);
+			let tones = self.controller.tick(&mut self.cw_ctx, input);
 
-			let color = match signal {
-				Some(_) => match self.controller.get_mode() {
+			let color = match tones.is_empty() {
+				false => match self.controller.get_mode() {
 					Mode::Output => OUTPUT_COLOR,
 					Mode::Input => INPUT_COLOR,
 				},
-				None => OFF_COLOR,
+				true => OFF_COLOR,
 			};
 
-			if self.audio.last_signal != signal {
-				self.audio.sink = get_audio_sink(&self.audio.stream_handle, signal);
+			if self.audio.last_tones != tones {
+				self.audio.sinks = get_audio_sinks(&self.audio.stream_handle, &tones);
 			}
 
-			self.audio.last_signal = signal;
+			self.audio.last_tones = tones;
 
 			draw_circle(ui, color);
+
+			if let Some(wpm) = self.controller.get_wpm() {
+				ui.label(format!("{wpm} WPM"));
+			}
+
+			ui.label(String::from(self.controller.get_transcript()));
 		});
 	}
 }
@@ -89,14 +105,16 @@ fn draw_circle(ui: &mut Ui, color: Color32) {
 	ui.painter().circle_filled(center, 100.0, color);
 }
 
-fn get_audio_sink(stream_handle: &OutputStreamHandle, freq: Option<u32>) -> Option<Sink> {
-	if let Some(freq) = freq {
-		let new_sink = Sink::try_new(stream_handle).unwrap();
-		let source = SineWave::new(freq as f32).amplify(0.1).repeat_infinite();
-		new_sink.append(source);
-		new_sink.play();
-		Some(new_sink)
-	} else {
-		None
-	}
+/// one sink per tone, so several voices can be heard at once
+fn get_audio_sinks(stream_handle: &OutputStreamHandle, tones: &[u32]) -> Vec<Sink> {
+	tones
+		.iter()
+		.map(|&freq| {
+			let new_sink = Sink::try_new(stream_handle).unwrap();
+			let source = SineWave::new(freq as f32).amplify(0.1).repeat_infinite();
+			new_sink.append(source);
+			new_sink.play();
+			new_sink
+		})
+		.collect()
 }